@@ -0,0 +1,375 @@
+//! Reader for the [aseprite file format](https://github.com/aseprite/aseprite/blob/main/docs/ase-file-specs.md)
+//!
+//! Only what is needed to export sprite sheets is supported: frames and their durations, layers (with visibility and
+//! opacity), image cels, tags and slices. Layers are always composed with the normal blend mode.
+
+use std::fmt::{self, Display, Formatter};
+
+pub use sheet::{ClipDescription, FrameDescription, Repeat, SheetDescription, SpriteSheet};
+
+mod png;
+mod reader;
+mod sheet;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Aseprite {
+    pub width: u16,
+    pub height: u16,
+    pub frames: Vec<Frame>,
+    pub layers: Vec<Layer>,
+    pub tags: Vec<Tag>,
+    pub slices: Vec<Slice>,
+    palette: Vec<[u8; 4]>,
+    color_depth: ColorDepth,
+    transparent_index: u8,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Frame {
+    pub duration_ms: u16,
+    pub cels: Vec<Cel>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: u8,
+    /// Depth of the layer in the layer hierarchy (`0` for top-level layers)
+    pub child_level: u16,
+    pub is_group: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cel {
+    pub layer: u16,
+    pub position: [i16; 2],
+    pub opacity: u8,
+    pub content: CelContent,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CelContent {
+    /// Pixels in the color depth of the file
+    Image {
+        width: u16,
+        height: u16,
+        pixels: Vec<u8>,
+    },
+    /// Same image as the cel of the same layer in another frame
+    Linked { frame: u16 },
+    /// Content not supported by this reader (e.g. tilemaps)
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// First frame of the tag (inclusive)
+    pub from: u16,
+    /// Last frame of the tag (inclusive)
+    pub to: u16,
+    pub direction: Direction,
+    /// Number of times the tag should be played (`0` meaning infinitely)
+    pub repeat: u16,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    Forward,
+    Reverse,
+    PingPong,
+    PingPongReverse,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Slice {
+    pub name: String,
+    pub keys: Vec<SliceKey>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SliceKey {
+    /// Frame from which this key applies
+    pub frame: u32,
+    pub position: [i32; 2],
+    pub size: [u32; 2],
+    /// Pivot, relative to the slice position
+    pub pivot: Option<[i32; 2]>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ColorDepth {
+    Rgba,
+    Grayscale,
+    Indexed,
+}
+
+impl ColorDepth {
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgba => 4,
+            Self::Grayscale => 2,
+            Self::Indexed => 1,
+        }
+    }
+}
+
+/// Error returned when an aseprite file cannot be read
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    UnexpectedEndOfFile,
+    InvalidMagicNumber,
+    UnsupportedColorDepth(u16),
+    InvalidString,
+    InvalidCompressedData,
+    InvalidCel,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEndOfFile => write!(f, "unexpected end of file"),
+            Self::InvalidMagicNumber => write!(f, "not an aseprite file (invalid magic number)"),
+            Self::UnsupportedColorDepth(depth) => write!(f, "unsupported color depth: {depth}"),
+            Self::InvalidString => write!(f, "invalid utf-8 string"),
+            Self::InvalidCompressedData => write!(f, "invalid compressed cel data"),
+            Self::InvalidCel => write!(f, "cel data does not match its size"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Aseprite {
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        reader::parse(bytes)
+    }
+
+    /// Render the visible layers of a frame into RGBA pixels (row-major, `width * height` pixels)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is out of bounds
+    #[must_use]
+    pub fn render_frame(&self, frame: usize) -> Vec<[u8; 4]> {
+        let mut pixels = vec![[0; 4]; usize::from(self.width) * usize::from(self.height)];
+        let mut cels: Vec<&Cel> = self.frames[frame].cels.iter().collect();
+        cels.sort_by_key(|cel| cel.layer);
+        for cel in cels {
+            let Some(layer) = self.layers.get(usize::from(cel.layer)) else {
+                continue;
+            };
+            if !self.is_visible(usize::from(cel.layer)) {
+                continue;
+            }
+            let Some((width, height, data)) = self.cel_image(frame, cel) else {
+                continue;
+            };
+            let opacity = u32::from(cel.opacity) * u32::from(layer.opacity) / 255;
+            self.draw_cel(&mut pixels, cel.position, [width, height], data, opacity);
+        }
+        pixels
+    }
+
+    /// Returns the pivot defined by the first slice having one, relative to the top-left of the frames
+    #[must_use]
+    pub fn pivot(&self) -> Option<[i32; 2]> {
+        self.slices.iter().flat_map(|s| &s.keys).find_map(|key| {
+            let [x, y] = key.pivot?;
+            Some([key.position[0] + x, key.position[1] + y])
+        })
+    }
+
+    /// A layer is visible if it and all of its parent groups are visible
+    fn is_visible(&self, index: usize) -> bool {
+        let mut level = self.layers[index].child_level;
+        if !self.layers[index].visible {
+            return false;
+        }
+        for layer in self.layers[..index].iter().rev() {
+            if level == 0 {
+                break;
+            }
+            if layer.child_level < level {
+                if !layer.visible {
+                    return false;
+                }
+                level = layer.child_level;
+            }
+        }
+        true
+    }
+
+    fn cel_image<'a>(&'a self, frame: usize, cel: &'a Cel) -> Option<(u16, u16, &'a [u8])> {
+        match &cel.content {
+            CelContent::Image {
+                width,
+                height,
+                pixels,
+            } => Some((*width, *height, pixels)),
+            CelContent::Linked { frame: linked } if usize::from(*linked) != frame => {
+                let linked_cel = self
+                    .frames
+                    .get(usize::from(*linked))?
+                    .cels
+                    .iter()
+                    .find(|c| c.layer == cel.layer)?;
+                self.cel_image(usize::from(*linked), linked_cel)
+            }
+            CelContent::Linked { .. } | CelContent::Unsupported => None,
+        }
+    }
+
+    fn draw_cel(
+        &self,
+        target: &mut [[u8; 4]],
+        [x0, y0]: [i16; 2],
+        [width, height]: [u16; 2],
+        data: &[u8],
+        opacity: u32,
+    ) {
+        let bytes_per_pixel = self.color_depth.bytes_per_pixel();
+        for y in 0..i32::from(height) {
+            let target_y = i32::from(y0) + y;
+            if target_y < 0 || target_y >= i32::from(self.height) {
+                continue;
+            }
+            for x in 0..i32::from(width) {
+                let target_x = i32::from(x0) + x;
+                if target_x < 0 || target_x >= i32::from(self.width) {
+                    continue;
+                }
+                let offset = (y * i32::from(width) + x) as usize * bytes_per_pixel;
+                let source = self.color(&data[offset..offset + bytes_per_pixel]);
+                let target = &mut target[(target_y * i32::from(self.width) + target_x) as usize];
+                *target = blend(*target, source, opacity);
+            }
+        }
+    }
+
+    fn color(&self, pixel: &[u8]) -> [u8; 4] {
+        match self.color_depth {
+            ColorDepth::Rgba => [pixel[0], pixel[1], pixel[2], pixel[3]],
+            ColorDepth::Grayscale => [pixel[0], pixel[0], pixel[0], pixel[1]],
+            ColorDepth::Indexed if pixel[0] == self.transparent_index => [0; 4],
+            ColorDepth::Indexed => self
+                .palette
+                .get(usize::from(pixel[0]))
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Compose `source` over `target` (normal blend mode)
+fn blend(target: [u8; 4], source: [u8; 4], opacity: u32) -> [u8; 4] {
+    let source_alpha = u32::from(source[3]) * opacity / 255;
+    if source_alpha == 0 {
+        return target;
+    }
+    let target_alpha = u32::from(target[3]);
+    let alpha = source_alpha + target_alpha * (255 - source_alpha) / 255;
+    let channel = |s: u8, t: u8| {
+        let value = (u32::from(s) * source_alpha
+            + u32::from(t) * target_alpha * (255 - source_alpha) / 255)
+            / alpha;
+        value as u8
+    };
+    [
+        channel(source[0], target[0]),
+        channel(source[1], target[1]),
+        channel(source[2], target[2]),
+        alpha as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader::builder;
+
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const NONE: [u8; 4] = [0; 4];
+
+    #[test]
+    fn renders_layers_from_bottom_to_top() {
+        let bytes = builder::file(
+            [2, 1],
+            &[(
+                100,
+                vec![
+                    builder::layer("background", true, 255),
+                    builder::layer("foreground", true, 255),
+                    builder::cel(1, [1, 0], [1, 1], &[BLUE]),
+                    builder::cel(0, [0, 0], [2, 1], &[RED, RED]),
+                ],
+            )],
+        );
+        let aseprite = Aseprite::parse(&bytes).unwrap();
+        assert_eq!(aseprite.render_frame(0), [RED, BLUE]);
+    }
+
+    #[test]
+    fn skips_hidden_layers() {
+        let bytes = builder::file(
+            [1, 1],
+            &[(
+                100,
+                vec![
+                    builder::layer("hidden", false, 255),
+                    builder::cel(0, [0, 0], [1, 1], &[RED]),
+                ],
+            )],
+        );
+        let aseprite = Aseprite::parse(&bytes).unwrap();
+        assert_eq!(aseprite.render_frame(0), [NONE]);
+    }
+
+    #[test]
+    fn applies_layer_opacity() {
+        let bytes = builder::file(
+            [1, 1],
+            &[(
+                100,
+                vec![
+                    builder::layer("ghost", true, 51),
+                    builder::cel(0, [0, 0], [1, 1], &[RED]),
+                ],
+            )],
+        );
+        let aseprite = Aseprite::parse(&bytes).unwrap();
+        assert_eq!(aseprite.render_frame(0), [[255, 0, 0, 51]]);
+    }
+
+    #[test]
+    fn renders_linked_cels() {
+        let bytes = builder::file(
+            [1, 1],
+            &[
+                (
+                    100,
+                    vec![
+                        builder::layer("body", true, 255),
+                        builder::cel(0, [0, 0], [1, 1], &[BLUE]),
+                    ],
+                ),
+                (100, vec![builder::linked_cel(0, 0)]),
+            ],
+        );
+        let aseprite = Aseprite::parse(&bytes).unwrap();
+        assert_eq!(aseprite.render_frame(1), [BLUE]);
+    }
+
+    #[test]
+    fn pivot_is_relative_to_frame() {
+        let bytes = builder::file(
+            [16, 16],
+            &[(100, vec![builder::slice("origin", [2, 4], [6, 12])])],
+        );
+        let aseprite = Aseprite::parse(&bytes).unwrap();
+        assert_eq!(aseprite.pivot(), Some([8, 16]));
+    }
+}
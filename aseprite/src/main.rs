@@ -0,0 +1,27 @@
+//! Generate a sprite sheet (png) and the description of its animation clips (json) from an aseprite file
+
+use std::{env, error::Error, fs, fs::File, io::BufWriter, process};
+
+use aseprite::{Aseprite, SpriteSheet};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [input, sheet, clips] = args.as_slice() else {
+        eprintln!("usage: aseprite-import <input.aseprite> <sheet.png> <clips.json>");
+        process::exit(2);
+    };
+    if let Err(err) = run(input, sheet, clips) {
+        eprintln!("cannot import {input}: {err}");
+        process::exit(1);
+    }
+}
+
+fn run(input: &str, sheet_path: &str, clips_path: &str) -> Result<(), Box<dyn Error>> {
+    let aseprite = Aseprite::parse(&fs::read(input)?)?;
+    let sheet = SpriteSheet::from_aseprite(&aseprite);
+    sheet.write_png(BufWriter::new(File::create(sheet_path)?))?;
+    let mut json = serde_json::to_string_pretty(&sheet.description)?;
+    json.push('\n');
+    fs::write(clips_path, json)?;
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Minimal PNG encoder for 8-bit RGBA images
+
+use std::io::{self, Write};
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const COLOR_TYPE_RGBA: u8 = 6;
+
+pub(crate) fn write_rgba(
+    mut writer: impl Write,
+    [width, height]: [u32; 2],
+    pixels: &[[u8; 4]],
+) -> io::Result<()> {
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    header.extend([8, COLOR_TYPE_RGBA, 0, 0, 0]); // bit depth, color type, compression, filter, interlace
+    let mut scanlines = Vec::with_capacity(pixels.len() * 4 + height as usize);
+    for row in pixels.chunks_exact((width as usize).max(1)) {
+        scanlines.push(0); // no filter
+        scanlines.extend(row.concat());
+    }
+    writer.write_all(SIGNATURE)?;
+    write_chunk(&mut writer, b"IHDR", &header)?;
+    write_chunk(
+        &mut writer,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&scanlines, 6),
+    )?;
+    write_chunk(&mut writer, b"IEND", &[])
+}
+
+fn write_chunk(writer: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    writer.write_all(&crc32(kind.iter().chain(data)).to_be_bytes())
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    !bytes.into_iter().fold(u32::MAX, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_of_iend_chunk() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn encodes_image() {
+        let mut bytes = Vec::new();
+        write_rgba(&mut bytes, [1, 1], &[[1, 2, 3, 4]]).unwrap();
+        assert!(bytes.starts_with(SIGNATURE));
+        assert!(bytes.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+        let idat_start = SIGNATURE.len() + 25 + 8;
+        let idat_len =
+            u32::from_be_bytes(bytes[idat_start - 8..idat_start - 4].try_into().unwrap());
+        let idat = &bytes[idat_start..idat_start + idat_len as usize];
+        assert_eq!(
+            miniz_oxide::inflate::decompress_to_vec_zlib(idat).unwrap(),
+            [0, 1, 2, 3, 4]
+        );
+    }
+}
@@ -0,0 +1,447 @@
+use crate::{
+    Aseprite, Cel, CelContent, ColorDepth, Direction, Error, Frame, Layer, Slice, SliceKey, Tag,
+};
+
+const HEADER_SIZE: usize = 128;
+const FILE_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+
+const LAYER_CHUNK: u16 = 0x2004;
+const CEL_CHUNK: u16 = 0x2005;
+const TAGS_CHUNK: u16 = 0x2018;
+const PALETTE_CHUNK: u16 = 0x2019;
+const SLICE_CHUNK: u16 = 0x2022;
+
+/// Header flag telling that the layer opacity is valid
+const LAYER_OPACITY_VALID: u32 = 1;
+
+pub(crate) fn parse(bytes: &[u8]) -> Result<Aseprite, Error> {
+    let mut reader = Reader(bytes);
+    let mut header = Reader(reader.take(HEADER_SIZE)?);
+    header.skip(4)?; // file size
+    if header.u16()? != FILE_MAGIC {
+        return Err(Error::InvalidMagicNumber);
+    }
+    let frames = header.u16()?;
+    let width = header.u16()?;
+    let height = header.u16()?;
+    let color_depth = match header.u16()? {
+        32 => ColorDepth::Rgba,
+        16 => ColorDepth::Grayscale,
+        8 => ColorDepth::Indexed,
+        depth => return Err(Error::UnsupportedColorDepth(depth)),
+    };
+    let flags = header.u32()?;
+    header.skip(2 + 4 + 4)?; // speed (deprecated) and reserved
+    let transparent_index = header.u8()?;
+    let mut aseprite = Aseprite {
+        width,
+        height,
+        frames: Vec::with_capacity(frames.into()),
+        layers: Vec::new(),
+        tags: Vec::new(),
+        slices: Vec::new(),
+        palette: Vec::new(),
+        color_depth,
+        transparent_index,
+    };
+    for _ in 0..frames {
+        let size = reader.u32()? as usize;
+        let mut frame = Reader(reader.take(size.saturating_sub(4))?);
+        if frame.u16()? != FRAME_MAGIC {
+            return Err(Error::InvalidMagicNumber);
+        }
+        let old_chunks_count = frame.u16()?;
+        let duration_ms = frame.u16()?;
+        frame.skip(2)?;
+        let chunks_count = match frame.u32()? {
+            0 => u32::from(old_chunks_count),
+            count => count,
+        };
+        let mut cels = Vec::new();
+        for _ in 0..chunks_count {
+            let size = frame.u32()? as usize;
+            let kind = frame.u16()?;
+            let mut chunk = Reader(frame.take(size.saturating_sub(6))?);
+            match kind {
+                LAYER_CHUNK => aseprite
+                    .layers
+                    .push(layer(&mut chunk, flags & LAYER_OPACITY_VALID != 0)?),
+                CEL_CHUNK => cels.push(cel(&mut chunk, color_depth)?),
+                TAGS_CHUNK => aseprite.tags.extend(tags(&mut chunk)?),
+                PALETTE_CHUNK => palette(&mut chunk, &mut aseprite.palette)?,
+                SLICE_CHUNK => aseprite.slices.push(slice(&mut chunk)?),
+                _ => (),
+            }
+        }
+        aseprite.frames.push(Frame { duration_ms, cels });
+    }
+    Ok(aseprite)
+}
+
+fn layer(chunk: &mut Reader<'_>, opacity_valid: bool) -> Result<Layer, Error> {
+    let flags = chunk.u16()?;
+    let kind = chunk.u16()?;
+    let child_level = chunk.u16()?;
+    chunk.skip(2 + 2 + 2)?; // default size and blend mode
+    let opacity = chunk.u8()?;
+    chunk.skip(3)?;
+    let name = chunk.string()?;
+    Ok(Layer {
+        name,
+        visible: flags & 1 != 0,
+        opacity: if opacity_valid { opacity } else { u8::MAX },
+        child_level,
+        is_group: kind == 1,
+    })
+}
+
+fn cel(chunk: &mut Reader<'_>, color_depth: ColorDepth) -> Result<Cel, Error> {
+    let layer = chunk.u16()?;
+    let position = [chunk.i16()?, chunk.i16()?];
+    let opacity = chunk.u8()?;
+    let kind = chunk.u16()?;
+    chunk.skip(2 + 5)?; // z-index and reserved
+    let content = match kind {
+        0 | 2 => {
+            let width = chunk.u16()?;
+            let height = chunk.u16()?;
+            let len = usize::from(width) * usize::from(height) * color_depth.bytes_per_pixel();
+            let pixels = if kind == 0 {
+                chunk.take(len)?.to_vec()
+            } else {
+                miniz_oxide::inflate::decompress_to_vec_zlib(chunk.0)
+                    .map_err(|_| Error::InvalidCompressedData)?
+            };
+            if pixels.len() != len {
+                return Err(Error::InvalidCel);
+            }
+            CelContent::Image {
+                width,
+                height,
+                pixels,
+            }
+        }
+        1 => CelContent::Linked {
+            frame: chunk.u16()?,
+        },
+        _ => CelContent::Unsupported,
+    };
+    Ok(Cel {
+        layer,
+        position,
+        opacity,
+        content,
+    })
+}
+
+fn tags(chunk: &mut Reader<'_>) -> Result<Vec<Tag>, Error> {
+    let count = chunk.u16()?;
+    chunk.skip(8)?;
+    (0..count)
+        .map(|_| {
+            let from = chunk.u16()?;
+            let to = chunk.u16()?;
+            let direction = match chunk.u8()? {
+                1 => Direction::Reverse,
+                2 => Direction::PingPong,
+                3 => Direction::PingPongReverse,
+                _ => Direction::Forward,
+            };
+            let repeat = chunk.u16()?;
+            chunk.skip(6 + 3 + 1)?; // reserved, color and extra byte
+            let name = chunk.string()?;
+            Ok(Tag {
+                name,
+                from,
+                to,
+                direction,
+                repeat,
+            })
+        })
+        .collect()
+}
+
+fn palette(chunk: &mut Reader<'_>, palette: &mut Vec<[u8; 4]>) -> Result<(), Error> {
+    let size = chunk.u32()? as usize;
+    let first = chunk.u32()? as usize;
+    let last = chunk.u32()? as usize;
+    chunk.skip(8)?;
+    palette.resize(size.max(palette.len()), [0; 4]);
+    for index in first..=last {
+        let flags = chunk.u16()?;
+        let color = [chunk.u8()?, chunk.u8()?, chunk.u8()?, chunk.u8()?];
+        if flags & 1 != 0 {
+            chunk.string()?;
+        }
+        if let Some(entry) = palette.get_mut(index) {
+            *entry = color;
+        }
+    }
+    Ok(())
+}
+
+fn slice(chunk: &mut Reader<'_>) -> Result<Slice, Error> {
+    let count = chunk.u32()?;
+    let flags = chunk.u32()?;
+    chunk.skip(4)?;
+    let name = chunk.string()?;
+    let keys = (0..count)
+        .map(|_| {
+            let frame = chunk.u32()?;
+            let position = [chunk.i32()?, chunk.i32()?];
+            let size = [chunk.u32()?, chunk.u32()?];
+            if flags & 1 != 0 {
+                chunk.skip(16)?; // 9-patch center
+            }
+            let pivot = if flags & 2 != 0 {
+                Some([chunk.i32()?, chunk.i32()?])
+            } else {
+                None
+            };
+            Ok(SliceKey {
+                frame,
+                position,
+                size,
+                pivot,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(Slice { name, keys })
+}
+
+/// Little-endian reader over a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Error> {
+        self.take(len).map(|_| ())
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i16(&mut self) -> Result<i16, Error> {
+        self.array().map(i16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let len = self.u16()?;
+        let bytes = self.take(len.into())?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidString)
+    }
+}
+
+/// Helpers to write aseprite files in tests
+#[cfg(test)]
+pub(crate) mod builder {
+    use super::*;
+
+    pub(crate) fn file(size: [u16; 2], frames: &[(u16, Vec<Vec<u8>>)]) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend(0u32.to_le_bytes()); // file size (not used by the reader)
+        header.extend(FILE_MAGIC.to_le_bytes());
+        header.extend((frames.len() as u16).to_le_bytes());
+        header.extend(size[0].to_le_bytes());
+        header.extend(size[1].to_le_bytes());
+        header.extend(32u16.to_le_bytes());
+        header.extend(LAYER_OPACITY_VALID.to_le_bytes());
+        header.resize(HEADER_SIZE, 0);
+        for (duration_ms, chunks) in frames {
+            let chunks_bytes: Vec<u8> = chunks.concat();
+            header.extend((16 + chunks_bytes.len() as u32).to_le_bytes());
+            header.extend(FRAME_MAGIC.to_le_bytes());
+            header.extend((chunks.len() as u16).to_le_bytes());
+            header.extend(duration_ms.to_le_bytes());
+            header.extend([0; 2]);
+            header.extend((chunks.len() as u32).to_le_bytes());
+            header.extend(chunks_bytes);
+        }
+        header
+    }
+
+    pub(crate) fn layer(name: &str, visible: bool, opacity: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(u16::from(visible).to_le_bytes());
+        data.extend([0; 10]); // type, child level, default size and blend mode
+        data.push(opacity);
+        data.extend([0; 3]);
+        data.extend(string(name));
+        chunk(LAYER_CHUNK, data)
+    }
+
+    pub(crate) fn cel(
+        layer: u16,
+        position: [i16; 2],
+        size: [u16; 2],
+        pixels: &[[u8; 4]],
+    ) -> Vec<u8> {
+        let mut data = cel_header(layer, position, 2);
+        data.extend(size[0].to_le_bytes());
+        data.extend(size[1].to_le_bytes());
+        data.extend(miniz_oxide::deflate::compress_to_vec_zlib(
+            &pixels.concat(),
+            6,
+        ));
+        chunk(CEL_CHUNK, data)
+    }
+
+    pub(crate) fn linked_cel(layer: u16, frame: u16) -> Vec<u8> {
+        let mut data = cel_header(layer, [0, 0], 1);
+        data.extend(frame.to_le_bytes());
+        chunk(CEL_CHUNK, data)
+    }
+
+    fn cel_header(layer: u16, position: [i16; 2], kind: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(layer.to_le_bytes());
+        data.extend(position[0].to_le_bytes());
+        data.extend(position[1].to_le_bytes());
+        data.push(u8::MAX);
+        data.extend(kind.to_le_bytes());
+        data.extend([0; 7]);
+        data
+    }
+
+    pub(crate) fn tags(tags: &[(&str, u16, u16, u8, u16)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend((tags.len() as u16).to_le_bytes());
+        data.extend([0; 8]);
+        for (name, from, to, direction, repeat) in tags {
+            data.extend(from.to_le_bytes());
+            data.extend(to.to_le_bytes());
+            data.push(*direction);
+            data.extend(repeat.to_le_bytes());
+            data.extend([0; 10]);
+            data.extend(string(name));
+        }
+        chunk(TAGS_CHUNK, data)
+    }
+
+    pub(crate) fn slice(name: &str, position: [i32; 2], pivot: [i32; 2]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(1u32.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+        data.extend([0; 4]);
+        data.extend(string(name));
+        data.extend(0u32.to_le_bytes());
+        data.extend(position[0].to_le_bytes());
+        data.extend(position[1].to_le_bytes());
+        data.extend([1u32.to_le_bytes(), 1u32.to_le_bytes()].concat());
+        data.extend(pivot[0].to_le_bytes());
+        data.extend(pivot[1].to_le_bytes());
+        chunk(SLICE_CHUNK, data)
+    }
+
+    fn chunk(kind: u16, data: Vec<u8>) -> Vec<u8> {
+        let mut chunk = Vec::new();
+        chunk.extend((data.len() as u32 + 6).to_le_bytes());
+        chunk.extend(kind.to_le_bytes());
+        chunk.extend(data);
+        chunk
+    }
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut bytes = (value.len() as u16).to_le_bytes().to_vec();
+        bytes.extend(value.as_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    #[test]
+    fn reads_frames_layers_tags_and_slices() {
+        let bytes = builder::file(
+            [2, 3],
+            &[
+                (
+                    100,
+                    vec![
+                        builder::layer("body", true, 255),
+                        builder::cel(0, [1, 2], [1, 1], &[RED]),
+                        builder::tags(&[("run", 0, 1, 2, 0)]),
+                        builder::slice("pivot", [1, 0], [0, 3]),
+                    ],
+                ),
+                (150, vec![builder::linked_cel(0, 0)]),
+            ],
+        );
+        let aseprite = parse(&bytes).unwrap();
+        assert_eq!([aseprite.width, aseprite.height], [2, 3]);
+        assert_eq!(aseprite.frames.len(), 2);
+        assert_eq!(aseprite.frames[1].duration_ms, 150);
+        assert_eq!(aseprite.layers[0].name, "body");
+        assert_eq!(
+            aseprite.frames[0].cels[0].content,
+            CelContent::Image {
+                width: 1,
+                height: 1,
+                pixels: RED.to_vec()
+            }
+        );
+        assert_eq!(
+            aseprite.frames[1].cels[0].content,
+            CelContent::Linked { frame: 0 }
+        );
+        assert_eq!(
+            aseprite.tags,
+            [Tag {
+                name: "run".into(),
+                from: 0,
+                to: 1,
+                direction: Direction::PingPong,
+                repeat: 0
+            }]
+        );
+        assert_eq!(aseprite.slices[0].keys[0].pivot, Some([0, 3]));
+    }
+
+    #[test]
+    fn rejects_invalid_magic_number() {
+        let mut bytes = builder::file([1, 1], &[]);
+        bytes[4] = 0;
+        assert_eq!(parse(&bytes), Err(Error::InvalidMagicNumber));
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let bytes = builder::file([1, 1], &[(100, vec![builder::layer("a", true, 255)])]);
+        assert_eq!(
+            parse(&bytes[..bytes.len() - 1]),
+            Err(Error::UnexpectedEndOfFile)
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Aseprite, Direction, Tag};
+
+/// Name of the clip containing all the frames, for files without any tag
+const DEFAULT_CLIP: &str = "default";
+
+/// All the frames of an aseprite file, laid out in a single row, and the description of its animation clips
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels (row-major)
+    pub pixels: Vec<[u8; 4]>,
+    pub description: SheetDescription,
+}
+
+/// Animation clips of a sprite sheet, in the format expected by the game's animation system
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SheetDescription {
+    /// Number of frames (columns) in the sprite sheet
+    pub columns: usize,
+    /// Origin of the sprite, relative to the top-left of a frame
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot: Option<[i32; 2]>,
+    pub clips: BTreeMap<String, ClipDescription>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ClipDescription {
+    pub frames: Vec<FrameDescription>,
+    pub repeat: Repeat,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FrameDescription {
+    /// Column of the frame in the sprite sheet
+    pub index: usize,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repeat {
+    Loop,
+    Once,
+    PingPong,
+}
+
+impl SpriteSheet {
+    /// Render every frame and create one clip per tag
+    ///
+    /// If the file has no tag, a single looping clip named `default` contains all the frames.
+    #[must_use]
+    pub fn from_aseprite(aseprite: &Aseprite) -> Self {
+        let frame_width = usize::from(aseprite.width);
+        let columns = aseprite.frames.len();
+        let width = frame_width * columns;
+        let mut pixels = vec![[0; 4]; width * usize::from(aseprite.height)];
+        for frame in 0..columns {
+            let rendered = aseprite.render_frame(frame);
+            for (y, row) in rendered.chunks_exact(frame_width.max(1)).enumerate() {
+                let start = y * width + frame * frame_width;
+                pixels[start..start + frame_width].copy_from_slice(row);
+            }
+        }
+        let clips = if aseprite.tags.is_empty() {
+            let all = Tag {
+                name: DEFAULT_CLIP.into(),
+                from: 0,
+                to: columns.saturating_sub(1) as u16,
+                direction: Direction::Forward,
+                repeat: 0,
+            };
+            [(all.name.clone(), clip(aseprite, &all))].into()
+        } else {
+            aseprite
+                .tags
+                .iter()
+                .map(|tag| (tag.name.clone(), clip(aseprite, tag)))
+                .collect()
+        };
+        Self {
+            width: width as u32,
+            height: aseprite.height.into(),
+            pixels,
+            description: SheetDescription {
+                columns,
+                pivot: aseprite.pivot(),
+                clips,
+            },
+        }
+    }
+
+    /// Write the sprite sheet image in the PNG format
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        crate::png::write_rgba(writer, [self.width, self.height], &self.pixels)
+    }
+}
+
+fn clip(aseprite: &Aseprite, tag: &Tag) -> ClipDescription {
+    let mut frames: Vec<FrameDescription> = (tag.from..=tag.to)
+        .filter_map(|index| {
+            let frame = aseprite.frames.get(usize::from(index))?;
+            Some(FrameDescription {
+                index: index.into(),
+                duration_ms: frame.duration_ms.into(),
+            })
+        })
+        .collect();
+    if matches!(
+        tag.direction,
+        Direction::Reverse | Direction::PingPongReverse
+    ) {
+        frames.reverse();
+    }
+    let repeat = match tag.direction {
+        Direction::PingPong | Direction::PingPongReverse => Repeat::PingPong,
+        Direction::Forward | Direction::Reverse if tag.repeat == 1 => Repeat::Once,
+        Direction::Forward | Direction::Reverse => Repeat::Loop,
+    };
+    ClipDescription { frames, repeat }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::reader::builder;
+
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    fn sheet(tags: &[(&str, u16, u16, u8, u16)]) -> SpriteSheet {
+        let mut first_frame = vec![
+            builder::layer("body", true, 255),
+            builder::cel(0, [0, 0], [1, 1], &[RED]),
+        ];
+        if !tags.is_empty() {
+            first_frame.push(builder::tags(tags));
+        }
+        let bytes = builder::file(
+            [1, 1],
+            &[
+                (100, first_frame),
+                (200, vec![builder::cel(0, [0, 0], [1, 1], &[BLUE])]),
+            ],
+        );
+        SpriteSheet::from_aseprite(&Aseprite::parse(&bytes).unwrap())
+    }
+
+    #[test]
+    fn lays_out_frames_in_a_row() {
+        let sheet = sheet(&[]);
+        assert_eq!([sheet.width, sheet.height], [2, 1]);
+        assert_eq!(sheet.pixels, [RED, BLUE]);
+        assert_eq!(sheet.description.columns, 2);
+    }
+
+    #[test]
+    fn creates_default_clip_without_tags() {
+        let clip = &sheet(&[]).description.clips[DEFAULT_CLIP];
+        assert_eq!(clip.repeat, Repeat::Loop);
+        assert_eq!(
+            clip.frames,
+            [
+                FrameDescription {
+                    index: 0,
+                    duration_ms: 100
+                },
+                FrameDescription {
+                    index: 1,
+                    duration_ms: 200
+                }
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(0, 0, Repeat::Loop, [0, 1])]
+    #[case(0, 1, Repeat::Once, [0, 1])]
+    #[case(1, 0, Repeat::Loop, [1, 0])]
+    #[case(2, 0, Repeat::PingPong, [0, 1])]
+    #[case(3, 0, Repeat::PingPong, [1, 0])]
+    fn creates_clips_from_tags(
+        #[case] direction: u8,
+        #[case] repeat: u16,
+        #[case] expected_repeat: Repeat,
+        #[case] expected_frames: [usize; 2],
+    ) {
+        let clip = &sheet(&[("run", 0, 1, direction, repeat)]).description.clips["run"];
+        assert_eq!(clip.repeat, expected_repeat);
+        let frames: Vec<usize> = clip.frames.iter().map(|f| f.index).collect();
+        assert_eq!(frames, expected_frames);
+    }
+
+    #[test]
+    fn description_round_trips_through_json() {
+        let description = sheet(&[("idle", 0, 0, 0, 0)]).description;
+        let json = serde_json::to_string(&description).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SheetDescription>(&json).unwrap(),
+            description
+        );
+    }
+}
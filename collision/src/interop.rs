@@ -0,0 +1,33 @@
+#[cfg(feature = "math2d")]
+mod math2d {
+    use math2d::Rect;
+
+    use crate::Aabb;
+
+    impl From<Rect<f32>> for Aabb {
+        fn from(rect: Rect<f32>) -> Self {
+            Self::from_min_max(rect.min, rect.max)
+        }
+    }
+
+    impl From<Aabb> for Rect<f32> {
+        fn from(aabb: Aabb) -> Self {
+            let [min_x, max_x] = aabb.x.into();
+            let [min_y, max_y] = aabb.y.into();
+            Rect::from_min_max([min_x, min_y], [max_x, max_y])
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn converts_from_and_into_rect() {
+            let rect = Rect::from_min_max([1., 2.], [3., 4.]);
+            let aabb = Aabb::from(rect);
+            assert_eq!(aabb, Aabb::from_min_max([1., 2.], [3., 4.]));
+            assert_eq!(Rect::from(aabb), rect);
+        }
+    }
+}
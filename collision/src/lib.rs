@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod interop;
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Aabb {
     pub x: Range,
@@ -15,6 +17,16 @@ impl Aabb {
         Self { x, y }
     }
 
+    /// Returns a copy of [self] moved by [offset]
+    #[must_use]
+    pub fn translated(self, offset: impl Into<[f32; 2]>) -> Self {
+        let [dx, dy] = offset.into();
+        Self {
+            x: self.x.translated(dx),
+            y: self.y.translated(dy),
+        }
+    }
+
     /// Returns true if [self] overlaps [other]
     pub fn collides(self, other: Self) -> bool {
         self.x.collides(other.x) && self.y.collides(other.y)
@@ -74,6 +86,14 @@ impl Range {
         self.max > other.min && self.min < other.max
     }
 
+    #[must_use]
+    pub fn translated(self, offset: f32) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
     #[cfg(any(feature = "std", feature = "libm"))]
     fn penetration(self, other: Self) -> Option<f32> {
         let p1 = Some(other.min - self.max).filter(|p| *p < 0.)?;
@@ -98,11 +118,18 @@ fn abs(v: f32) -> f32 {
     libm::fabsf(v)
 }
 
-#[cfg(all(test, any(feature = "std", feature = "libm")))]
+#[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    fn translated_moves_both_ends() {
+        let aabb = Aabb::from_min_max([0., 1.], [2., 3.]).translated([1., -1.]);
+        assert_eq!(aabb, Aabb::from_min_max([1., 0.], [3., 2.]));
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
     fn range_should_penetrate_self() {
         let range = Range::from_min_max(0., 1.);
         assert_eq!(range.penetration(range).map(abs), Some(1.0));
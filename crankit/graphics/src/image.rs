@@ -7,7 +7,7 @@ use core::{
 
 use playdate_sys::ffi::{LCDBitmap, LCDBitmapDrawMode, LCDBitmapFlip};
 
-use crate::{gfx, with_draw_context, Color, LoadError};
+use crate::{gfx, with_draw_context, Color, LoadError, Rect};
 
 /// An image that can be loaded from file ([`ImageOwned::from_path`) or created in memory ([`ImageOwned::from_size`]) to be drawn on screen.
 pub struct Image {
@@ -60,6 +60,35 @@ impl Image {
         unsafe { gfx().drawBitmap.unwrap()(self.ptr, x, y, flip) }
     }
 
+    /// Draws the `source` region of the image with its upper-left corner at the given `position`
+    ///
+    /// The region is drawn by clipping, so no intermediate image is allocated. The clip rect set by
+    /// [`crate::with_clip_rect`] still applies, and is restored afterward.
+    pub fn draw_region(&self, position: impl Into<[i32; 2]>, source: Rect, flip: Flip) {
+        let [x, y] = position.into();
+        let [width, height] = self.size();
+        let [mut source_x, mut source_y] = source.top_left;
+        let [source_width, source_height] = source.size;
+        if matches!(flip, Flip::FlippedX | Flip::FlippedXY) {
+            source_x = width - source_x - source_width;
+        }
+        if matches!(flip, Flip::FlippedY | Flip::FlippedXY) {
+            source_y = height - source_y - source_height;
+        }
+        let region = Rect::new([x, y], [source_width, source_height]);
+        let previous_clip = crate::clip_rect();
+        let clip = match previous_clip {
+            Some(previous) => previous.intersection(region),
+            None => Some(region),
+        };
+        let Some(clip) = clip else {
+            return;
+        };
+        crate::set_clip_rect(Some(clip));
+        unsafe { gfx().drawBitmap.unwrap()(self.ptr, x - source_x, y - source_y, flip.into()) }
+        crate::set_clip_rect(previous_clip);
+    }
+
     /// Draws the image with its upper-left corner at [position] tiled inside the rectangle of [size]
     pub fn draw_tiled(&self, position: impl Into<[i32; 2]>, size: impl Into<[i32; 2]>) {
         self.draw_tiled_with_flip(position, size, Flip::Unflipped);
@@ -0,0 +1,13 @@
+use crate::Rect;
+
+impl From<math2d::Rect<i32>> for Rect {
+    fn from(rect: math2d::Rect<i32>) -> Self {
+        Self::new(rect.min, rect.size())
+    }
+}
+
+impl From<Rect> for math2d::Rect<i32> {
+    fn from(rect: Rect) -> Self {
+        Self::from_min_size(rect.top_left, rect.size)
+    }
+}
@@ -2,19 +2,23 @@
 
 pub mod color;
 pub mod image;
+pub mod sheet;
 mod interop {
+    #[cfg(feature = "math2d")]
+    mod math2d;
     pub(crate) mod playdate_sys_v02;
 }
 
 extern crate alloc;
 
-use core::ptr;
+use core::{ffi::c_void, ptr};
 
 pub use color::Color;
 use image::Image;
 use interop::playdate_sys_v02::with_lcd_color;
-use playdate_sys::api;
+use playdate_sys::{api, ffi::PDStringEncoding};
 
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
 pub struct Rect {
     pub top_left: [i32; 2],
     pub size: [i32; 2],
@@ -27,6 +31,19 @@ impl Rect {
             size: size.into(),
         }
     }
+
+    /// Returns the area covered by both rectangles, if any
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let axis = |a: usize| {
+            let min = self.top_left[a].max(other.top_left[a]);
+            let max = (self.top_left[a] + self.size[a]).min(other.top_left[a] + other.size[a]);
+            (max > min).then_some([min, max - min])
+        };
+        let [x, width] = axis(0)?;
+        let [y, height] = axis(1)?;
+        Some(Self::new([x, y], [width, height]))
+    }
 }
 
 unsafe fn gfx() -> &'static playdate_sys::ffi::playdate_graphics {
@@ -65,6 +82,31 @@ pub fn draw_ellipse(rect: Rect, line_width: i32, color: impl Into<Color>) {
     draw_ellipse_with_angle(rect, line_width, 0., 360., color);
 }
 
+/// Fills the rectangle with `color`
+pub fn fill_rect(rect: Rect, color: impl Into<Color>) {
+    let [x, y] = rect.top_left;
+    let [w, h] = rect.size;
+    with_lcd_color(color, |color| unsafe {
+        gfx().fillRect.unwrap()(x, y, w, h, color);
+    });
+}
+
+/// Draws `text` with its upper-left corner at `position`, using the current font
+///
+/// Returns the width of the drawn text, in pixels.
+pub fn draw_text(text: &str, position: impl Into<[i32; 2]>) -> i32 {
+    let [x, y] = position.into();
+    unsafe {
+        gfx().drawText.unwrap()(
+            text.as_ptr().cast::<c_void>(),
+            text.len(),
+            PDStringEncoding::kUTF8Encoding,
+            x,
+            y,
+        )
+    }
+}
+
 /// Draws a line from `p1` to `p2` with a stroke width of `width`
 pub fn draw_line(
     p1: impl Into<[i32; 2]>,
@@ -79,6 +121,47 @@ pub fn draw_line(
     });
 }
 
+/// Restricts drawing to `rect` while executing `draw`
+///
+/// The rect is affected by the draw offset (see [`set_draw_offset`]).
+/// The previous clip rect, if any, is restored once `draw` returns.
+pub fn with_clip_rect(rect: Rect, draw: impl FnOnce()) {
+    let previous = clip_rect();
+    set_clip_rect(Some(rect));
+    draw();
+    set_clip_rect(previous);
+}
+
+/// Clip rect set by [`with_clip_rect`], kept because the playdate API does not allow to read it back
+static mut CLIP_RECT: Option<Rect> = None;
+
+/// Returns the clip rect currently set, if any
+pub(crate) fn clip_rect() -> Option<Rect> {
+    unsafe { CLIP_RECT }
+}
+
+/// Sets the clip rect, or clears it if `rect` is `None`
+pub(crate) fn set_clip_rect(rect: Option<Rect>) {
+    unsafe {
+        CLIP_RECT = rect;
+        match rect {
+            Some(Rect {
+                top_left: [x, y],
+                size: [w, h],
+            }) => gfx().setClipRect.unwrap()(x, y, w, h),
+            None => gfx().clearClipRect.unwrap()(),
+        }
+    }
+}
+
+/// Offsets the origin of all subsequent drawing calls by `offset`
+///
+/// Set it back to `[0, 0]` to stop offsetting.
+pub fn set_draw_offset(offset: impl Into<[i32; 2]>) {
+    let [dx, dy] = offset.into();
+    unsafe { gfx().setDrawOffset.unwrap()(dx, dy) }
+}
+
 pub fn draw_fps(position: impl Into<[i32; 2]>) {
     let [x, y] = position.into();
     unsafe {
@@ -104,11 +187,15 @@ pub fn with_draw_context<'a>(target: impl Into<Option<&'a mut Image>>, draw: imp
         Some(img) => unsafe { img.as_ptr() },
         None => ptr::null_mut(),
     };
+    // The pushed context starts without clip rect
+    let clip = clip_rect();
     unsafe {
+        CLIP_RECT = None;
         gfx().pushContext.unwrap()(ptr);
     }
     draw();
     unsafe {
         gfx().popContext.unwrap()();
+        CLIP_RECT = clip;
     }
 }
@@ -0,0 +1,348 @@
+//! Images containing multiple frames (sprite sheets and texture atlases)
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{
+    image::{Flip, Image},
+    Rect,
+};
+
+/// Layout of a grid of equally sized cells in an image
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct GridLayout {
+    pub cell_size: [i32; 2],
+    /// Space between the edges of the image and the cells
+    pub margin: [i32; 2],
+    /// Space between two adjacent cells
+    pub spacing: [i32; 2],
+}
+
+impl GridLayout {
+    #[must_use]
+    pub fn new(cell_size: impl Into<[i32; 2]>) -> Self {
+        Self {
+            cell_size: cell_size.into(),
+            margin: [0, 0],
+            spacing: [0, 0],
+        }
+    }
+
+    /// Layout of `columns` x `rows` cells covering the whole image (without margin nor spacing)
+    #[must_use]
+    pub fn from_columns_rows(image_size: impl Into<[i32; 2]>, [columns, rows]: [usize; 2]) -> Self {
+        let [width, height] = image_size.into();
+        Self::new([width / columns.max(1) as i32, height / rows.max(1) as i32])
+    }
+
+    #[must_use]
+    pub fn with_margin(mut self, margin: impl Into<[i32; 2]>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: impl Into<[i32; 2]>) -> Self {
+        self.spacing = spacing.into();
+        self
+    }
+
+    /// Returns the number of columns and rows that fit in an image of the given size
+    #[must_use]
+    pub fn count(&self, image_size: impl Into<[i32; 2]>) -> [usize; 2] {
+        let image_size = image_size.into();
+        let count = |axis: usize| {
+            let available = image_size[axis] - 2 * self.margin[axis] + self.spacing[axis];
+            let step = self.cell_size[axis] + self.spacing[axis];
+            if step <= 0 || available <= 0 {
+                0
+            } else {
+                (available / step) as usize
+            }
+        };
+        [count(0), count(1)]
+    }
+
+    /// Returns the regions of the cells of an image of the given size, row by row
+    pub fn cells(&self, image_size: impl Into<[i32; 2]>) -> impl Iterator<Item = Rect> + '_ {
+        let [columns, rows] = self.count(image_size);
+        (0..rows).flat_map(move |row| {
+            (0..columns).map(move |column| {
+                let x = self.margin[0] + column as i32 * (self.cell_size[0] + self.spacing[0]);
+                let y = self.margin[1] + row as i32 * (self.cell_size[1] + self.spacing[1]);
+                Rect::new([x, y], self.cell_size)
+            })
+        })
+    }
+}
+
+/// Named regions of a texture atlas
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Atlas {
+    pub frames: Vec<AtlasFrame>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AtlasFrame {
+    pub name: String,
+    pub region: Rect,
+}
+
+#[cfg(feature = "json")]
+impl Atlas {
+    /// Parse an atlas description in the JSON format used by TexturePacker (and Aseprite)
+    ///
+    /// Both the "hash" format (`frames` is an object keyed by name) and the "array" format (`frames` is a list with
+    /// a `filename` for each frame) are supported. In the "hash" format, frames are sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or does not describe an atlas
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let raw: json::Atlas = serde_json::from_str(json)?;
+        let frames = match raw.frames {
+            json::Frames::Hash(frames) => frames
+                .into_iter()
+                .map(|(name, frame)| AtlasFrame {
+                    name,
+                    region: frame.frame.into(),
+                })
+                .collect(),
+            json::Frames::Array(frames) => frames
+                .into_iter()
+                .map(|frame| AtlasFrame {
+                    name: frame.filename,
+                    region: frame.frame.into(),
+                })
+                .collect(),
+        };
+        Ok(Self { frames })
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+    use serde::Deserialize;
+
+    use crate::Rect;
+
+    #[derive(Deserialize)]
+    pub(super) struct Atlas {
+        pub(super) frames: Frames,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum Frames {
+        Hash(BTreeMap<String, Frame>),
+        Array(Vec<NamedFrame>),
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct Frame {
+        pub(super) frame: Region,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct NamedFrame {
+        pub(super) filename: String,
+        pub(super) frame: Region,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct Region {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    }
+
+    impl From<Region> for Rect {
+        fn from(Region { x, y, w, h }: Region) -> Self {
+            Rect::new([x, y], [w, h])
+        }
+    }
+}
+
+/// An image containing multiple frames, each drawn from its region of the image
+///
+/// Unlike [`Image::split_columns`], no image is allocated per frame.
+pub struct SpriteSheet {
+    image: Image,
+    frames: Vec<Rect>,
+    names: BTreeMap<String, usize>,
+}
+
+impl SpriteSheet {
+    /// Create a sprite sheet from the cells of a grid (ordered row by row)
+    #[must_use]
+    pub fn from_grid(image: Image, layout: GridLayout) -> Self {
+        let frames = layout.cells(image.size()).collect();
+        Self {
+            image,
+            frames,
+            names: BTreeMap::new(),
+        }
+    }
+
+    /// Create a sprite sheet from the frames of an atlas (in the same order)
+    #[must_use]
+    pub fn from_atlas(image: Image, atlas: &Atlas) -> Self {
+        let frames = atlas.frames.iter().map(|frame| frame.region).collect();
+        let names = atlas
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| (frame.name.clone(), index))
+            .collect();
+        Self {
+            image,
+            frames,
+            names,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the region of the frame at `index`
+    #[must_use]
+    pub fn frame(&self, index: usize) -> Option<Rect> {
+        self.frames.get(index).copied()
+    }
+
+    /// Returns the index of the frame named `name` (only for sprite sheets created from an atlas)
+    #[must_use]
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Draws the frame at `index` with its upper-left corner at the given `position`
+    ///
+    /// Nothing is drawn if there is no frame at `index`.
+    pub fn draw(&self, index: usize, position: impl Into<[i32; 2]>, flip: Flip) {
+        if let Some(region) = self.frame(index) {
+            self.image.draw_region(position, region, flip);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(GridLayout::new([16, 16]), [64, 32], [4, 2])]
+    #[case(GridLayout::new([16, 16]), [70, 40], [4, 2])]
+    #[case(GridLayout::new([16, 16]), [8, 8], [0, 0])]
+    #[case(GridLayout::new([16, 8]).with_margin([2, 1]), [36, 18], [2, 2])]
+    #[case(GridLayout::new([16, 16]).with_spacing([1, 2]), [50, 34], [3, 2])]
+    #[case(GridLayout::new([16, 16]).with_spacing([1, 2]), [49, 33], [2, 1])]
+    #[case(GridLayout::new([10, 10]).with_margin([1, 1]).with_spacing([2, 2]), [36, 24], [3, 2])]
+    #[case(GridLayout::new([10, 10]).with_margin([1, 1]).with_spacing([2, 2]), [35, 23], [2, 1])]
+    #[case(GridLayout::new([0, 0]), [64, 64], [0, 0])]
+    #[case(GridLayout::new([16, 16]).with_margin([40, 40]), [64, 64], [0, 0])]
+    fn grid_count(
+        #[case] layout: GridLayout,
+        #[case] image_size: [i32; 2],
+        #[case] expected: [usize; 2],
+    ) {
+        assert_eq!(layout.count(image_size), expected);
+    }
+
+    #[test]
+    fn grid_cells_are_ordered_row_by_row() {
+        let layout = GridLayout::new([10, 8])
+            .with_margin([1, 2])
+            .with_spacing([3, 4]);
+        let cells: Vec<Rect> = layout.cells([25, 32]).collect();
+        assert_eq!(
+            cells,
+            [
+                Rect::new([1, 2], [10, 8]),
+                Rect::new([14, 2], [10, 8]),
+                Rect::new([1, 14], [10, 8]),
+                Rect::new([14, 14], [10, 8]),
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_from_columns_rows() {
+        let layout = GridLayout::from_columns_rows([70, 20], [7, 1]);
+        assert_eq!(layout, GridLayout::new([10, 20]));
+        assert_eq!(layout.cells([70, 20]).count(), 7);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn atlas_from_json_hash() {
+        let json = r#"{
+            "frames": {
+                "run 1": { "frame": { "x": 16, "y": 0, "w": 16, "h": 24 }, "duration": 100 },
+                "idle": { "frame": { "x": 0, "y": 0, "w": 16, "h": 24 }, "duration": 100 }
+            },
+            "meta": { "image": "player.png" }
+        }"#;
+        let atlas = Atlas::from_json(json).unwrap();
+        assert_eq!(
+            atlas.frames,
+            [
+                AtlasFrame {
+                    name: "idle".into(),
+                    region: Rect::new([0, 0], [16, 24]),
+                },
+                AtlasFrame {
+                    name: "run 1".into(),
+                    region: Rect::new([16, 0], [16, 24]),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn atlas_from_json_array_keeps_order() {
+        let json = r#"{
+            "frames": [
+                { "filename": "b", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } },
+                { "filename": "a", "frame": { "x": 8, "y": 0, "w": 8, "h": 4 } }
+            ]
+        }"#;
+        let atlas = Atlas::from_json(json).unwrap();
+        let names: Vec<&str> = atlas.frames.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["b", "a"]);
+        assert_eq!(atlas.frames[1].region, Rect::new([8, 0], [8, 4]));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn atlas_from_invalid_json() {
+        let invalid = [
+            "",
+            "{}",
+            r#"{ "frames": 3 }"#,
+            r#"{ "frames": [{ "filename": "a" }] }"#,
+        ];
+        for json in invalid {
+            assert!(Atlas::from_json(json).is_err(), "{json}");
+        }
+    }
+}
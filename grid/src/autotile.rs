@@ -0,0 +1,284 @@
+//! Compute tile indices from the neighborhood of each cell
+//!
+//! Cells outside the grid are considered "filled", so that the terrain connects seamlessly to the borders
+//! of the map.
+
+use crate::Grid;
+
+/// Set of neighbors that are "filled" around a cell
+///
+/// Each bit represents a direction (see the associated constants).
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Neighbors(pub u8);
+
+impl Neighbors {
+    pub const NORTH: Self = Self(1);
+    pub const NORTH_EAST: Self = Self(1 << 1);
+    pub const EAST: Self = Self(1 << 2);
+    pub const SOUTH_EAST: Self = Self(1 << 3);
+    pub const SOUTH: Self = Self(1 << 4);
+    pub const SOUTH_WEST: Self = Self(1 << 5);
+    pub const WEST: Self = Self(1 << 6);
+    pub const NORTH_WEST: Self = Self(1 << 7);
+
+    /// Offsets of each neighbor, in the order of the bits
+    const OFFSETS: [[isize; 2]; 8] = [
+        [0, -1],
+        [1, -1],
+        [1, 0],
+        [1, 1],
+        [0, 1],
+        [-1, 1],
+        [-1, 0],
+        [-1, -1],
+    ];
+
+    /// Returns true if all the neighbors of `other` are contained in `self`
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the 4-bit mask of the cardinal neighbors (north = 1, east = 2, south = 4, west = 8)
+    #[must_use]
+    pub fn cardinal_mask(self) -> u8 {
+        [Self::NORTH, Self::EAST, Self::SOUTH, Self::WEST]
+            .into_iter()
+            .enumerate()
+            .filter(|(_, n)| self.contains(*n))
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Returns the neighbors where corners are removed unless both adjacent sides are also present
+    ///
+    /// This reduces the 256 possible combinations down to 47.
+    #[must_use]
+    pub fn without_isolated_corners(self) -> Self {
+        let corners = [
+            (Self::NORTH_EAST, Self::NORTH, Self::EAST),
+            (Self::SOUTH_EAST, Self::SOUTH, Self::EAST),
+            (Self::SOUTH_WEST, Self::SOUTH, Self::WEST),
+            (Self::NORTH_WEST, Self::NORTH, Self::WEST),
+        ];
+        let mut result = self;
+        for (corner, side1, side2) in corners {
+            if !self.contains(side1) || !self.contains(side2) {
+                result.0 &= !corner.0;
+            }
+        }
+        result
+    }
+
+    /// Returns the 4-bit mask of the "filled" corners (north-east = 1, south-east = 2, south-west = 4, north-west = 8)
+    ///
+    /// A corner is filled if the two adjacent sides and the diagonal neighbor are all filled.
+    #[must_use]
+    pub fn corner_mask(self) -> u8 {
+        let reduced = self.without_isolated_corners();
+        [
+            Self::NORTH_EAST,
+            Self::SOUTH_EAST,
+            Self::SOUTH_WEST,
+            Self::NORTH_WEST,
+        ]
+        .into_iter()
+        .enumerate()
+        .filter(|(_, n)| reduced.contains(*n))
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Returns the index (in `0..47`) of the blob tile for these neighbors
+    ///
+    /// Tiles are ordered by ascending value of the reduced mask (see [`Self::without_isolated_corners`]).
+    #[must_use]
+    pub fn blob_index(self) -> usize {
+        let reduced = self.without_isolated_corners().0;
+        BLOB_MASKS
+            .iter()
+            .position(|m| *m == reduced)
+            .expect("reduced neighbors should always be a valid blob mask")
+    }
+}
+
+/// Rules mapping the neighborhood of a filled cell to a tile index
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Rules {
+    /// One tile per combination of cardinal neighbors, indexed by [`Neighbors::cardinal_mask`]
+    Bitmask([u16; 16]),
+    /// One tile per combination of neighbors without isolated corners, indexed by [`Neighbors::blob_index`]
+    Blob([u16; 47]),
+    /// One tile per combination of filled corners, indexed by [`Neighbors::corner_mask`]
+    Wang([u16; 16]),
+}
+
+impl Rules {
+    /// Returns the tile index to use for a filled cell surrounded by `neighbors`
+    #[must_use]
+    pub fn tile(&self, neighbors: Neighbors) -> u16 {
+        match self {
+            Self::Bitmask(tiles) => tiles[neighbors.cardinal_mask() as usize],
+            Self::Blob(tiles) => tiles[neighbors.blob_index()],
+            Self::Wang(tiles) => tiles[neighbors.corner_mask() as usize],
+        }
+    }
+}
+
+/// Returns the set of filled neighbors around the cell at `coord`
+pub fn neighbors<T>(
+    grid: &Grid<T>,
+    coord: impl Into<[usize; 2]>,
+    is_filled: impl Fn(&T) -> bool,
+) -> Neighbors {
+    let [x, y] = coord.into();
+    let mut result = Neighbors::default();
+    for (bit, [dx, dy]) in Neighbors::OFFSETS.into_iter().enumerate() {
+        let filled = match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+            (Some(nx), Some(ny)) => grid.get([nx, ny]).map(&is_filled).unwrap_or(true),
+            _ => true,
+        };
+        if filled {
+            result.0 |= 1 << bit;
+        }
+    }
+    result
+}
+
+/// Returns the tile index for the cell at `coord`
+///
+/// Returns `None` if the cell is not filled or is out of the grid.
+pub fn tile_at<T>(
+    grid: &Grid<T>,
+    coord: impl Into<[usize; 2]>,
+    rules: &Rules,
+    is_filled: impl Fn(&T) -> bool,
+) -> Option<u16> {
+    let coord = coord.into();
+    if !is_filled(grid.get(coord)?) {
+        return None;
+    }
+    Some(rules.tile(neighbors(grid, coord, is_filled)))
+}
+
+/// Returns a grid of the same size containing the tile index of every filled cell
+pub fn autotile<T>(
+    grid: &Grid<T>,
+    rules: &Rules,
+    is_filled: impl Fn(&T) -> bool,
+) -> Grid<Option<u16>> {
+    let width = grid.width();
+    let height = grid.height();
+    let tiles = (0..height)
+        .flat_map(|y| (0..width).map(move |x| [x, y]))
+        .map(|coord| tile_at(grid, coord, rules, &is_filled));
+    Grid::from_iter(width, height, tiles)
+}
+
+/// Rules for the stone terrain of `assets/tilemap.png` (20 columns of 16x16 tiles), as set up in the LDtk auto-layer
+///
+/// LDtk randomly picks a variant for cells surrounded by terrain, only the most common one is used here.
+pub const TILEMAP_STONE: Rules = Rules::Blob([
+    338, 318, 335, 315, 315, 278, 298, 275, 295, 295, 275, 295, 295, 337, 317, 336, 316, 316, 277,
+    297, 276, 339, 339, 276, 339, 339, 277, 297, 276, 339, 339, 276, 339, 339, 317, 316, 316, 297,
+    279, 299, 279, 319, 297, 279, 299, 279, 131,
+]);
+
+/// All the masks of neighbors without isolated corners, in ascending order
+const BLOB_MASKS: [u8; 47] = [
+    0, 1, 4, 5, 7, 16, 17, 20, 21, 23, 28, 29, 31, 64, 65, 68, 69, 71, 80, 81, 84, 85, 87, 92, 93,
+    95, 112, 113, 116, 117, 119, 124, 125, 127, 193, 197, 199, 209, 213, 215, 221, 223, 241, 245,
+    247, 253, 255,
+];
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<bool> {
+        let width = rows[0].len();
+        let cells = rows.iter().flat_map(|r| r.chars().map(|c| c == '#'));
+        Grid::from_iter(width, rows.len(), cells)
+    }
+
+    #[test]
+    fn blob_masks_are_exactly_the_reduced_masks() {
+        let mut reduced: alloc::vec::Vec<u8> = (0..=255u8)
+            .map(|m| Neighbors(m).without_isolated_corners().0)
+            .collect();
+        reduced.sort_unstable();
+        reduced.dedup();
+        assert_eq!(reduced, BLOB_MASKS);
+    }
+
+    #[test]
+    fn cells_out_of_the_grid_are_filled() {
+        let grid = grid(&["#"]);
+        assert_eq!(neighbors(&grid, [0, 0], |c| *c), Neighbors(255));
+    }
+
+    #[rstest]
+    #[case([2, 1], 0b0100)]
+    #[case([1, 2], 0b0010)]
+    #[case([2, 2], 0b1111)]
+    #[case([3, 2], 0b1000)]
+    #[case([2, 3], 0b0001)]
+    fn bitmask_uses_cardinal_neighbors(#[case] coord: [usize; 2], #[case] expected: u16) {
+        let grid = grid(&[".....", "..#..", ".###.", "..#..", "....."]);
+        let tiles: [u16; 16] = core::array::from_fn(|i| i as u16);
+        assert_eq!(
+            tile_at(&grid, coord, &Rules::Bitmask(tiles), |c| *c),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn empty_cells_have_no_tile() {
+        let grid = grid(&["#.", ".#"]);
+        let tiles = autotile(&grid, &Rules::Bitmask([0; 16]), |c| *c);
+        assert_eq!(tiles.get([1, 0]), Some(&None));
+        assert_eq!(tiles.get([0, 1]), Some(&None));
+        assert_eq!(tiles.get([0, 0]), Some(&Some(0)));
+    }
+
+    #[test]
+    fn blob_ignores_corners_without_both_sides() {
+        let grid = grid(&[".....", ".#.#.", ".....", ".#...", "....."]);
+        assert_eq!(neighbors(&grid, [2, 2], |c| *c).blob_index(), 0);
+    }
+
+    #[rstest]
+    #[case([2, 2], 131)]
+    #[case([2, 1], 276)]
+    #[case([1, 1], 275)]
+    #[case([3, 3], 317)]
+    #[case([1, 2], 295)]
+    fn tilemap_stone_edges_and_corners(#[case] coord: [usize; 2], #[case] expected: u16) {
+        let grid = grid(&[".....", ".###.", ".###.", ".###.", "....."]);
+        assert_eq!(
+            tile_at(&grid, coord, &TILEMAP_STONE, |c| *c),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn tilemap_stone_isolated_cell_and_inner_corner() {
+        let isolated = grid(&["...", ".#.", "..."]);
+        assert_eq!(
+            tile_at(&isolated, [1, 1], &TILEMAP_STONE, |c| *c),
+            Some(338)
+        );
+        let inner_corner = grid(&["..###", "..###", "#####", "#####", "#####"]);
+        assert_eq!(
+            tile_at(&inner_corner, [2, 2], &TILEMAP_STONE, |c| *c),
+            Some(339)
+        );
+    }
+
+    #[test]
+    fn wang_uses_filled_corners() {
+        let grid = grid(&["##.", "##.", "..."]);
+        let corners = neighbors(&grid, [1, 1], |c| *c).corner_mask();
+        assert_eq!(corners, 0b1000);
+    }
+}
@@ -4,6 +4,11 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
+pub use tracked::TrackedGrid;
+
+pub mod autotile;
+mod tracked;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grid<T> {
     cells: Vec<T>,
@@ -62,6 +67,14 @@ impl<T> Grid<T> {
             None
         }
     }
+
+    /// Returns the coordinate of the cell at `index`
+    ///
+    /// This is the inverse of [`Self::index_of`]
+    #[must_use]
+    pub fn coord_of(&self, index: usize) -> [usize; 2] {
+        [index % self.width, index / self.width]
+    }
 }
 
 impl<T: Default> Grid<T> {
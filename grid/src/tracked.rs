@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::Grid;
+
+/// A [`Grid`] that keeps track of the changes made to its cells
+///
+/// Every change is recorded in an undo log, so that the grid can be reverted to its initial state without
+/// cloning it again. Changed cells are also marked as "dirty" until [`TrackedGrid::clear_dirty`] is called,
+/// so that renderers can re-draw only what changed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TrackedGrid<T> {
+    grid: Grid<T>,
+    undo_log: Vec<(usize, T)>,
+    dirty: Vec<usize>,
+    is_dirty: Vec<bool>,
+}
+
+impl<T> TrackedGrid<T> {
+    #[must_use]
+    pub fn new(grid: Grid<T>) -> Self {
+        let mut is_dirty = Vec::new();
+        is_dirty.resize(grid.width() * grid.height(), false);
+        Self {
+            grid,
+            undo_log: Vec::new(),
+            dirty: Vec::new(),
+            is_dirty,
+        }
+    }
+
+    /// Replace the cell at `coord`, recording the previous value in the undo log
+    ///
+    /// Returns the previous value, or `None` if `coord` is out of the grid.
+    pub fn set(&mut self, coord: impl Into<[usize; 2]>, cell: T) -> Option<T>
+    where
+        T: Clone,
+    {
+        let index = self.grid.index_of(coord)?;
+        let previous = core::mem::replace(&mut self.grid.cells[index], cell);
+        self.undo_log.push((index, previous.clone()));
+        self.mark_dirty(index);
+        Some(previous)
+    }
+
+    /// Revert the last change
+    ///
+    /// Returns `false` if there was no change to revert
+    pub fn undo(&mut self) -> bool {
+        let Some((index, previous)) = self.undo_log.pop() else {
+            return false;
+        };
+        self.grid.cells[index] = previous;
+        self.mark_dirty(index);
+        true
+    }
+
+    /// Revert all the changes, restoring the grid as it was when created (or last committed)
+    pub fn reset(&mut self) {
+        while self.undo() {}
+    }
+
+    /// Forget the undo log, so that the current state becomes the one restored by [`Self::reset`]
+    pub fn commit(&mut self) {
+        self.undo_log.clear();
+    }
+
+    /// Returns true if there is any change that can be reverted
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !self.undo_log.is_empty()
+    }
+
+    /// Returns true if the cell at `coord` changed since the last call to [`Self::clear_dirty`]
+    #[must_use]
+    pub fn is_dirty(&self, coord: impl Into<[usize; 2]>) -> bool {
+        self.grid
+            .index_of(coord)
+            .is_some_and(|index| self.is_dirty[index])
+    }
+
+    /// Iterate over the coordinates of the cells that changed since the last call to [`Self::clear_dirty`]
+    pub fn dirty_cells(&self) -> impl Iterator<Item = [usize; 2]> + '_ {
+        self.dirty.iter().map(|index| self.grid.coord_of(*index))
+    }
+
+    /// Returns the smallest region (`[min, max]`, both inclusive) containing all the dirty cells
+    ///
+    /// Returns `None` if there is no dirty cell
+    #[must_use]
+    pub fn dirty_region(&self) -> Option<[[usize; 2]; 2]> {
+        self.dirty_cells().fold(None, |region, [x, y]| {
+            Some(match region {
+                None => [[x, y], [x, y]],
+                Some([[min_x, min_y], [max_x, max_y]]) => {
+                    [[min_x.min(x), min_y.min(y)], [max_x.max(x), max_y.max(y)]]
+                }
+            })
+        })
+    }
+
+    /// Mark all cells as clean
+    pub fn clear_dirty(&mut self) {
+        for index in self.dirty.drain(..) {
+            self.is_dirty[index] = false;
+        }
+    }
+
+    fn mark_dirty(&mut self, index: usize) {
+        if !self.is_dirty[index] {
+            self.is_dirty[index] = true;
+            self.dirty.push(index);
+        }
+    }
+}
+
+impl<T> Deref for TrackedGrid<T> {
+    type Target = Grid<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grid
+    }
+}
+
+impl<T> From<Grid<T>> for TrackedGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::new(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> TrackedGrid<u8> {
+        Grid::from_iter(3, 2, [0, 1, 2, 3, 4, 5]).into()
+    }
+
+    #[test]
+    fn set_returns_previous_value() {
+        let mut grid = grid();
+        assert_eq!(grid.set([1, 1], 9), Some(4));
+        assert_eq!(grid.get([1, 1]), Some(&9));
+        assert_eq!(grid.set([3, 0], 9), None);
+    }
+
+    #[test]
+    fn undo_reverts_last_change() {
+        let mut grid = grid();
+        grid.set([0, 0], 7);
+        grid.set([0, 0], 8);
+        assert!(grid.undo());
+        assert_eq!(grid.get([0, 0]), Some(&7));
+        assert!(grid.undo());
+        assert_eq!(grid.get([0, 0]), Some(&0));
+        assert!(!grid.undo());
+    }
+
+    #[test]
+    fn reset_reverts_all_changes() {
+        let mut grid = grid();
+        grid.set([0, 0], 7);
+        grid.set([2, 1], 8);
+        grid.set([0, 0], 9);
+        grid.reset();
+        assert_eq!(*grid, *self::grid());
+        assert!(!grid.has_changes());
+    }
+
+    #[test]
+    fn reset_reverts_to_last_commit() {
+        let mut grid = grid();
+        grid.set([0, 0], 7);
+        grid.commit();
+        grid.set([0, 0], 8);
+        grid.reset();
+        assert_eq!(grid.get([0, 0]), Some(&7));
+    }
+
+    #[test]
+    fn changed_cells_are_dirty_until_cleared() {
+        let mut grid = grid();
+        assert!(grid.dirty_region().is_none());
+        grid.set([0, 1], 7);
+        grid.set([2, 0], 8);
+        grid.set([0, 1], 9);
+        assert!(grid.is_dirty([0, 1]));
+        assert!(!grid.is_dirty([1, 1]));
+        assert_eq!(grid.dirty_cells().count(), 2);
+        assert_eq!(grid.dirty_region(), Some([[0, 0], [2, 1]]));
+        grid.clear_dirty();
+        assert!(!grid.is_dirty([0, 1]));
+        assert_eq!(grid.dirty_cells().count(), 0);
+    }
+
+    #[test]
+    fn undone_cells_are_dirty() {
+        let mut grid = grid();
+        grid.set([1, 0], 7);
+        grid.clear_dirty();
+        grid.undo();
+        assert!(grid.is_dirty([1, 0]));
+    }
+}
@@ -1,3 +1,5 @@
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
 pub trait Angle: Copy {
     fn cos(self) -> f32;
     fn sin(self) -> f32;
@@ -70,3 +72,215 @@ impl Angle for Degrees {
         Radians::from(self).sin()
     }
 }
+
+/// Implement arithmetic and normalization for an angle type, where `$half_turn` is the value of a half turn
+macro_rules! impl_angle_ops {
+    ($angle:ident, $half_turn:expr) => {
+        impl $angle {
+            /// Returns the angle wrapped into the range `[0, full turn)`
+            #[must_use]
+            #[cfg(any(feature = "std", feature = "libm"))]
+            pub fn normalize_positive(self) -> Self {
+                let full_turn = 2. * $half_turn;
+                let wrapped = self.0 - crate::floor(self.0 / full_turn) * full_turn;
+                Self(if wrapped >= full_turn { 0. } else { wrapped })
+            }
+
+            /// Returns the angle wrapped into the range `(-half turn, half turn]`
+            #[must_use]
+            #[cfg(any(feature = "std", feature = "libm"))]
+            pub fn normalize_signed(self) -> Self {
+                let positive = self.normalize_positive();
+                if positive.0 > $half_turn {
+                    Self(positive.0 - 2. * $half_turn)
+                } else {
+                    positive
+                }
+            }
+
+            /// Returns the smallest signed angle to add to `self` to get the direction of `to`
+            ///
+            /// The result is in the range `(-half turn, half turn]`
+            #[must_use]
+            #[cfg(any(feature = "std", feature = "libm"))]
+            pub fn shortest_difference(self, to: Self) -> Self {
+                (to - self).normalize_signed()
+            }
+
+            /// Interpolate from `self` to `to` following the shortest path
+            ///
+            /// Returns `self` when `t` is `0` and an angle with the same direction as `to` when `t` is `1`.
+            #[must_use]
+            #[cfg(any(feature = "std", feature = "libm"))]
+            pub fn lerp_angle(self, to: Self, t: f32) -> Self {
+                self + self.shortest_difference(to) * t
+            }
+        }
+
+        impl Add for $angle {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $angle {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $angle {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $angle {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $angle {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $angle {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<f32> for $angle {
+            type Output = Self;
+
+            fn div(self, rhs: f32) -> Self::Output {
+                Self(self.0 / rhs)
+            }
+        }
+    };
+}
+
+impl_angle_ops!(Radians, core::f32::consts::PI);
+impl_angle_ops!(Degrees, 180.);
+
+impl Radians {
+    /// Returns the angle of the vector (`x`, `y`) relative to the x axis
+    ///
+    /// The result is in the range `-PI..=PI`
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn atan2(y: f32, x: f32) -> Self {
+        Self(crate::atan2(y, x))
+    }
+}
+
+impl Degrees {
+    /// Returns the angle of the vector (`x`, `y`) relative to the x axis
+    ///
+    /// The result is in the range `-180..=180`
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn atan2(y: f32, x: f32) -> Self {
+        Radians::atan2(y, x).into()
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod tests {
+    use core::f32::consts::PI;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 0.0001, "{actual} != {expected}");
+    }
+
+    #[rstest]
+    #[case(0., 0., 0.)]
+    #[case(90., 90., 90.)]
+    #[case(360., 0., 0.)]
+    #[case(370., 10., 10.)]
+    #[case(-90., 270., -90.)]
+    #[case(180., 180., 180.)]
+    #[case(-180., 180., 180.)]
+    #[case(540., 180., 180.)]
+    #[case(-725., 355., -5.)]
+    fn test_normalize_degrees(
+        #[case] angle: f32,
+        #[case] expected_positive: f32,
+        #[case] expected_signed: f32,
+    ) {
+        assert_near(Degrees(angle).normalize_positive().0, expected_positive);
+        assert_near(Degrees(angle).normalize_signed().0, expected_signed);
+    }
+
+    #[rstest]
+    #[case(0., 0., 0.)]
+    #[case(2. * PI, 0., 0.)]
+    #[case(-PI / 2., 3. * PI / 2., -PI / 2.)]
+    #[case(-PI, PI, PI)]
+    #[case(5. * PI + 1., PI + 1., 1. - PI)]
+    fn test_normalize_radians(
+        #[case] angle: f32,
+        #[case] expected_positive: f32,
+        #[case] expected_signed: f32,
+    ) {
+        assert_near(Radians(angle).normalize_positive().0, expected_positive);
+        assert_near(Radians(angle).normalize_signed().0, expected_signed);
+    }
+
+    #[rstest]
+    #[case(10., 20., 10.)]
+    #[case(20., 10., -10.)]
+    #[case(350., 10., 20.)]
+    #[case(10., 350., -20.)]
+    #[case(-170., 170., -20.)]
+    #[case(0., 720., 0.)]
+    fn test_shortest_difference(#[case] from: f32, #[case] to: f32, #[case] expected: f32) {
+        assert_near(Degrees(from).shortest_difference(Degrees(to)).0, expected);
+    }
+
+    #[rstest]
+    #[case(350., 10., 0.5, 360.)]
+    #[case(10., 350., 0.25, 5.)]
+    #[case(0., 90., 1., 90.)]
+    fn test_lerp_angle(#[case] from: f32, #[case] to: f32, #[case] t: f32, #[case] expected: f32) {
+        assert_near(Degrees(from).lerp_angle(Degrees(to), t).0, expected);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(Degrees(10.) + Degrees(20.), Degrees(30.));
+        assert_eq!(Degrees(10.) - Degrees(20.), Degrees(-10.));
+        assert_eq!(-Degrees(10.), Degrees(-10.));
+        assert_eq!(Degrees(10.) * 3., Degrees(30.));
+        assert_eq!(Radians(PI) / 2., Radians(PI / 2.));
+        let mut angle = Radians(1.);
+        angle += Radians(2.);
+        angle -= Radians(0.5);
+        assert_eq!(angle, Radians(2.5));
+    }
+
+    #[rstest]
+    #[case(0., 1., 0.)]
+    #[case(1., 0., 90.)]
+    #[case(0., -1., 180.)]
+    #[case(-1., -1., -135.)]
+    fn test_atan2(#[case] y: f32, #[case] x: f32, #[case] expected: f32) {
+        assert_near(Degrees::atan2(y, x).0, expected);
+        assert_near(Radians::atan2(y, x).0, expected.to_radians());
+    }
+}
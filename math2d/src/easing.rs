@@ -0,0 +1,197 @@
+/// Standard easing curves
+///
+/// Each curve maps a progress `t` in `0..=1` to an eased progress, where `0` maps to `0` and `1` maps to `1`.
+/// The `In` curves start slowly, the `Out` curves end slowly, and the `InOut` curves do both.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    SineIn,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    SineOut,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    SineInOut,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    ExpoIn,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    ExpoOut,
+    #[cfg(any(feature = "std", feature = "libm"))]
+    ExpoInOut,
+    /// Slightly goes backward before moving toward the end
+    BackIn,
+    /// Slightly overshoots the end before settling
+    BackOut,
+    BackInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    SmoothStep,
+}
+
+/// Overshoot amount used by the `Back` curves
+const BACK: f32 = 1.701_58;
+
+impl Easing {
+    /// Returns the eased value of `t`
+    ///
+    /// `t` is clamped in `0..=1`
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Self::Linear => t,
+            Self::QuadIn => t * t,
+            Self::QuadOut => out(t, |t| t * t),
+            Self::QuadInOut => in_out(t, |t| t * t),
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => out(t, |t| t * t * t),
+            Self::CubicInOut => in_out(t, |t| t * t * t),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::SineIn => sine_in(t),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::SineOut => out(t, sine_in),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::SineInOut => in_out(t, sine_in),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::ExpoIn => expo_in(t),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::ExpoOut => out(t, expo_in),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            Self::ExpoInOut => in_out(t, expo_in),
+            Self::BackIn => back_in(t),
+            Self::BackOut => out(t, back_in),
+            Self::BackInOut => in_out(t, back_in),
+            Self::BounceIn => 1. - bounce_out(1. - t),
+            Self::BounceOut => bounce_out(t),
+            Self::BounceInOut => in_out(t, |t| 1. - bounce_out(1. - t)),
+            Self::SmoothStep => t * t * (3. - 2. * t),
+        }
+    }
+}
+
+/// Mirror an "in" curve to get the corresponding "out" curve
+fn out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    1. - ease_in(1. - t)
+}
+
+/// Combine an "in" curve for the first half and its "out" curve for the second half
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.) / 2.
+    } else {
+        1. - ease_in((1. - t) * 2.) / 2.
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+fn sine_in(t: f32) -> f32 {
+    1. - crate::cos(t * core::f32::consts::FRAC_PI_2)
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+fn expo_in(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else {
+        crate::powf(2., 10. * t - 10.)
+    }
+}
+
+fn back_in(t: f32) -> f32 {
+    t * t * ((BACK + 1.) * t - BACK)
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn starts_at_zero_and_ends_at_one(
+        #[values(
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::BackIn,
+            Easing::BackOut,
+            Easing::BackInOut,
+            Easing::BounceIn,
+            Easing::BounceOut,
+            Easing::BounceInOut,
+            Easing::SmoothStep
+        )]
+        easing: Easing,
+    ) {
+        assert!(easing.apply(0.).abs() < 0.00001, "{easing:?}");
+        assert!((easing.apply(1.) - 1.).abs() < 0.00001, "{easing:?}");
+    }
+
+    #[rstest]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn transcendental_curves_start_at_zero_and_end_at_one(
+        #[values(
+            Easing::SineIn,
+            Easing::SineOut,
+            Easing::SineInOut,
+            Easing::ExpoIn,
+            Easing::ExpoOut,
+            Easing::ExpoInOut
+        )]
+        easing: Easing,
+    ) {
+        assert!(easing.apply(0.).abs() < 0.00001, "{easing:?}");
+        assert!((easing.apply(1.) - 1.).abs() < 0.00001, "{easing:?}");
+    }
+
+    #[rstest]
+    #[case(Easing::QuadIn, 0.25)]
+    #[case(Easing::QuadOut, 0.75)]
+    #[case(Easing::QuadInOut, 0.5)]
+    #[case(Easing::CubicIn, 0.125)]
+    #[case(Easing::CubicOut, 0.875)]
+    #[case(Easing::SmoothStep, 0.5)]
+    fn test_half_way(#[case] easing: Easing, #[case] expected: f32) {
+        assert!((easing.apply(0.5) - expected).abs() < 0.00001);
+    }
+
+    #[test]
+    fn back_in_goes_backward_first() {
+        assert!(Easing::BackIn.apply(0.2) < 0.);
+    }
+
+    #[test]
+    fn input_is_clamped() {
+        assert_eq!(Easing::QuadIn.apply(2.), 1.);
+        assert_eq!(Easing::QuadIn.apply(-1.), 0.);
+    }
+}
@@ -0,0 +1,631 @@
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::vector::Vector;
+
+/// Number of bits used for the fractional part
+const FRAC_BITS: u32 = 16;
+
+/// Raw value of `1.0`
+const ONE_RAW: i32 = 1 << FRAC_BITS;
+
+/// A signed fixed-point number with 16 bits of integer part and 16 bits of fractional part (Q16.16)
+///
+/// All operations, including [`Fixed::sqrt`], [`Fixed::sin`], [`Fixed::cos`] and [`Fixed::atan2`], are
+/// performed with integer arithmetic (and lookup tables), so that results are bit-for-bit identical
+/// on every platform.
+///
+/// The representable range is approximately `-32768.0..32768.0` with a precision of `1/65536`.
+/// Like with the primitive integers, arithmetic overflow panics in debug builds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(ONE_RAW);
+    pub const HALF: Self = Self(ONE_RAW / 2);
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+
+    /// Smallest positive value
+    pub const EPSILON: Self = Self(1);
+
+    pub const PI: Self = Self(205_887);
+    pub const FRAC_PI_2: Self = Self(102_944);
+    pub const TAU: Self = Self(411_775);
+
+    /// Create a fixed-point number from its raw bits representation
+    #[must_use]
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits representation
+    #[must_use]
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn from_int(value: i16) -> Self {
+        Self((value as i32) << FRAC_BITS)
+    }
+
+    /// Create a fixed-point number from the nearest representable value of `value`
+    ///
+    /// Values out of range are saturated, and `NaN` is converted to zero.
+    #[must_use]
+    pub fn from_f32(value: f32) -> Self {
+        let scaled = value * ONE_RAW as f32;
+        Self(if scaled < 0. {
+            (scaled - 0.5) as i32
+        } else {
+            (scaled + 0.5) as i32
+        })
+    }
+
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / ONE_RAW as f32
+    }
+
+    /// Returns the largest integer less than or equal to `self`
+    #[must_use]
+    pub const fn floor(self) -> Self {
+        Self(self.0 & !(ONE_RAW - 1))
+    }
+
+    /// Returns the smallest integer greater than or equal to `self`
+    ///
+    /// Overflows (panicking in debug builds) if `self` is greater than the largest integer.
+    #[must_use]
+    pub const fn ceil(self) -> Self {
+        Self::floor(Self(self.0 + (ONE_RAW - 1)))
+    }
+
+    /// Returns the nearest integer (half-way cases toward positive infinity)
+    #[must_use]
+    pub const fn round(self) -> Self {
+        Self::floor(Self(self.0 + ONE_RAW / 2))
+    }
+
+    /// Returns the fractional part (always positive, so that `self == self.floor() + self.fract()`)
+    #[must_use]
+    pub const fn fract(self) -> Self {
+        Self(self.0 & (ONE_RAW - 1))
+    }
+
+    /// Returns the integer part, rounded down
+    #[must_use]
+    pub const fn to_int(self) -> i32 {
+        self.0 >> FRAC_BITS
+    }
+
+    /// Returns the absolute value
+    ///
+    /// Overflows (panicking in debug builds) if `self` is [`Fixed::MIN`].
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    /// Returns the square root
+    ///
+    /// Returns `None` if `self` is negative
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        let raw = u64::try_from(self.0).ok()?;
+        Some(Self(isqrt(raw << FRAC_BITS) as i32))
+    }
+
+    /// Returns the sine of `self` (in radians)
+    #[must_use]
+    pub fn sin(self) -> Self {
+        Self(sin_turn(to_turn(self)))
+    }
+
+    /// Returns the cosine of `self` (in radians)
+    #[must_use]
+    pub fn cos(self) -> Self {
+        Self(sin_turn(to_turn(self) + QUARTER_TURN))
+    }
+
+    /// Returns the angle (in radians, in the range `-PI..=PI`) of the vector (`x`, `y`)
+    ///
+    /// Returns zero if both `x` and `y` are zero
+    #[must_use]
+    pub fn atan2(y: Self, x: Self) -> Self {
+        let (ax, ay) = (i64::from(x.0).abs(), i64::from(y.0).abs());
+        if ax == 0 && ay == 0 {
+            return Self::ZERO;
+        }
+        let mut angle = if ax >= ay {
+            atan_unit(((ay << FRAC_BITS) / ax) as i32)
+        } else {
+            Self::FRAC_PI_2.0 - atan_unit(((ax << FRAC_BITS) / ay) as i32)
+        };
+        if x.0 < 0 {
+            angle = Self::PI.0 - angle;
+        }
+        if y.0 < 0 {
+            angle = -angle;
+        }
+        Self(angle)
+    }
+}
+
+impl From<i16> for Fixed {
+    fn from(value: i16) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl From<Fixed> for f32 {
+    fn from(value: Fixed) -> Self {
+        value.to_f32()
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.to_f32(), f)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(narrow((i64::from(self.0) * i64::from(rhs.0)) >> FRAC_BITS))
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self(narrow((i64::from(self.0) << FRAC_BITS) / i64::from(rhs.0)))
+    }
+}
+
+impl DivAssign for Fixed {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+/// Converts the raw result of an operation computed with extended precision
+///
+/// Like the primitive integer operations, this panics on overflow in debug builds, and wraps in release builds.
+fn narrow(raw: i64) -> i32 {
+    debug_assert!(
+        i32::try_from(raw).is_ok(),
+        "attempt to compute a Fixed out of range"
+    );
+    raw as i32
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Vector<Fixed> {
+    /// Zero vector
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO);
+
+    /// Unit vector pointing in direction of the x axis
+    pub const X: Self = Self::new(Fixed::ONE, Fixed::ZERO);
+
+    /// Unit vector pointing in direction of the y axis
+    pub const Y: Self = Self::new(Fixed::ZERO, Fixed::ONE);
+
+    /// Magnitude (aka length) of the vector
+    ///
+    /// The squared magnitude is computed with extended precision, so this does not overflow even if
+    /// [`Self::magnitude_squared`] would. Magnitudes beyond the representable range saturate to
+    /// [`Fixed::MAX`].
+    #[must_use]
+    pub fn magnitude(self) -> Fixed {
+        let x = u64::from(self.x.0.unsigned_abs());
+        let y = u64::from(self.y.0.unsigned_abs());
+        let magnitude = isqrt(x * x + y * y);
+        Fixed(i32::try_from(magnitude).unwrap_or(i32::MAX))
+    }
+
+    /// Return the normalized version of the vector if possible
+    ///
+    /// If the vector cannot be normalized, returns `None` (e.g. if the vector has a magnitude of zero)
+    #[must_use]
+    pub fn normalize(self) -> Option<Self> {
+        let magnitude = self.magnitude();
+        if magnitude == Fixed::ZERO {
+            None
+        } else {
+            Some(Self::new(self.x / magnitude, self.y / magnitude))
+        }
+    }
+
+    /// Returns the angle of the vector, relative to the x axis, in radians
+    #[must_use]
+    pub fn angle(self) -> Fixed {
+        Fixed::atan2(self.y, self.x)
+    }
+
+    /// cast into a [`Vector<f32>`]
+    #[must_use]
+    pub fn as_vector_f32(self) -> Vector<f32> {
+        Vector::new(self.x.to_f32(), self.y.to_f32())
+    }
+}
+
+impl Vector<f32> {
+    /// Convert into a [`Vector<Fixed>`] (see [`Fixed::from_f32`])
+    #[must_use]
+    pub fn as_vector_fixed(self) -> Vector<Fixed> {
+        Vector::new(Fixed::from_f32(self.x), Fixed::from_f32(self.y))
+    }
+}
+
+/// Integer square root (rounded down)
+fn isqrt(value: u64) -> u64 {
+    let mut remainder = value;
+    let mut result = 0;
+    let mut bit = 1 << 62;
+    while bit > value {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if remainder >= result + bit {
+            remainder -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+        bit >>= 2;
+    }
+    result
+}
+
+/// Number of turn units in a quarter turn (a full turn is `1 << 16`)
+const QUARTER_TURN: i32 = 1 << 14;
+
+/// Convert an angle in radians into turn units (a full turn is `1 << 16`)
+fn to_turn(angle: Fixed) -> i32 {
+    ((i64::from(angle.0) << FRAC_BITS) / i64::from(Fixed::TAU.0)) as i32
+}
+
+/// Sine of an angle given in turn units
+fn sin_turn(turn: i32) -> i32 {
+    let turn = turn & 0xFFFF;
+    let within = turn & (QUARTER_TURN - 1);
+    match turn / QUARTER_TURN {
+        0 => sin_quarter(within),
+        1 => sin_quarter(QUARTER_TURN - within),
+        2 => -sin_quarter(within),
+        _ => -sin_quarter(QUARTER_TURN - within),
+    }
+}
+
+/// Sine of an angle between 0 and a quarter turn (inclusive), in turn units
+fn sin_quarter(turn: i32) -> i32 {
+    interpolate(&SIN_TABLE, turn, 6)
+}
+
+/// Arc-tangent of a value between 0 and 1 (inclusive)
+fn atan_unit(value: i32) -> i32 {
+    interpolate(&ATAN_TABLE, value, 8)
+}
+
+/// Linear interpolation in a table of 257 entries, where `shift` is the number of bits between entries
+fn interpolate(table: &[i32; 257], value: i32, shift: u32) -> i32 {
+    let index = (value >> shift) as usize;
+    let frac = value & ((1 << shift) - 1);
+    match table.get(index + 1) {
+        None => table[index],
+        Some(next) => table[index] + (((next - table[index]) * frac) >> shift),
+    }
+}
+
+/// `sin(x)` for x in `0..=PI/2` (257 evenly distributed samples)
+#[rustfmt::skip]
+const SIN_TABLE: [i32; 257] = [
+    0, 402, 804, 1206, 1608, 2010, 2412, 2814, 3216, 3617,
+    4019, 4420, 4821, 5222, 5623, 6023, 6424, 6824, 7224, 7623,
+    8022, 8421, 8820, 9218, 9616, 10014, 10411, 10808, 11204, 11600,
+    11996, 12391, 12785, 13180, 13573, 13966, 14359, 14751, 15143, 15534,
+    15924, 16314, 16703, 17091, 17479, 17867, 18253, 18639, 19024, 19409,
+    19792, 20175, 20557, 20939, 21320, 21699, 22078, 22457, 22834, 23210,
+    23586, 23961, 24335, 24708, 25080, 25451, 25821, 26190, 26558, 26925,
+    27291, 27656, 28020, 28383, 28745, 29106, 29466, 29824, 30182, 30538,
+    30893, 31248, 31600, 31952, 32303, 32652, 33000, 33347, 33692, 34037,
+    34380, 34721, 35062, 35401, 35738, 36075, 36410, 36744, 37076, 37407,
+    37736, 38064, 38391, 38716, 39040, 39362, 39683, 40002, 40320, 40636,
+    40951, 41264, 41576, 41886, 42194, 42501, 42806, 43110, 43412, 43713,
+    44011, 44308, 44604, 44898, 45190, 45480, 45769, 46056, 46341, 46624,
+    46906, 47186, 47464, 47741, 48015, 48288, 48559, 48828, 49095, 49361,
+    49624, 49886, 50146, 50404, 50660, 50914, 51166, 51417, 51665, 51911,
+    52156, 52398, 52639, 52878, 53114, 53349, 53581, 53812, 54040, 54267,
+    54491, 54714, 54934, 55152, 55368, 55582, 55794, 56004, 56212, 56418,
+    56621, 56823, 57022, 57219, 57414, 57607, 57798, 57986, 58172, 58356,
+    58538, 58718, 58896, 59071, 59244, 59415, 59583, 59750, 59914, 60075,
+    60235, 60392, 60547, 60700, 60851, 60999, 61145, 61288, 61429, 61568,
+    61705, 61839, 61971, 62101, 62228, 62353, 62476, 62596, 62714, 62830,
+    62943, 63054, 63162, 63268, 63372, 63473, 63572, 63668, 63763, 63854,
+    63944, 64031, 64115, 64197, 64277, 64354, 64429, 64501, 64571, 64639,
+    64704, 64766, 64827, 64884, 64940, 64993, 65043, 65091, 65137, 65180,
+    65220, 65259, 65294, 65328, 65358, 65387, 65413, 65436, 65457, 65476,
+    65492, 65505, 65516, 65525, 65531, 65535, 65536,
+];
+
+/// `atan(x)` for x in `0..=1` (257 evenly distributed samples)
+#[rustfmt::skip]
+const ATAN_TABLE: [i32; 257] = [
+    0, 256, 512, 768, 1024, 1280, 1536, 1792, 2047, 2303,
+    2559, 2814, 3070, 3325, 3580, 3836, 4091, 4346, 4600, 4855,
+    5110, 5364, 5618, 5872, 6126, 6380, 6633, 6887, 7140, 7392,
+    7645, 7898, 8150, 8402, 8653, 8905, 9156, 9407, 9657, 9908,
+    10158, 10408, 10657, 10906, 11155, 11403, 11652, 11899, 12147, 12394,
+    12641, 12887, 13133, 13379, 13624, 13869, 14114, 14358, 14601, 14845,
+    15088, 15330, 15572, 15814, 16055, 16296, 16536, 16776, 17015, 17254,
+    17492, 17730, 17968, 18205, 18441, 18677, 18913, 19148, 19382, 19616,
+    19850, 20083, 20315, 20547, 20779, 21009, 21240, 21469, 21699, 21927,
+    22156, 22383, 22610, 22836, 23062, 23288, 23512, 23737, 23960, 24183,
+    24406, 24627, 24849, 25069, 25289, 25509, 25727, 25946, 26163, 26380,
+    26597, 26813, 27028, 27242, 27456, 27670, 27882, 28094, 28306, 28517,
+    28727, 28936, 29145, 29354, 29561, 29768, 29975, 30180, 30386, 30590,
+    30794, 30997, 31200, 31402, 31603, 31803, 32003, 32203, 32401, 32600,
+    32797, 32994, 33190, 33385, 33580, 33774, 33968, 34160, 34353, 34544,
+    34735, 34925, 35115, 35304, 35492, 35680, 35867, 36053, 36239, 36424,
+    36608, 36792, 36975, 37158, 37340, 37521, 37701, 37881, 38060, 38239,
+    38417, 38594, 38771, 38947, 39123, 39297, 39472, 39645, 39818, 39990,
+    40162, 40333, 40503, 40673, 40842, 41010, 41178, 41346, 41512, 41678,
+    41844, 42008, 42172, 42336, 42499, 42661, 42823, 42984, 43145, 43304,
+    43464, 43622, 43780, 43938, 44095, 44251, 44407, 44562, 44716, 44870,
+    45024, 45176, 45328, 45480, 45631, 45781, 45931, 46080, 46229, 46377,
+    46525, 46672, 46818, 46964, 47109, 47254, 47398, 47542, 47685, 47827,
+    47969, 48111, 48251, 48392, 48531, 48671, 48809, 48947, 49085, 49222,
+    49359, 49495, 49630, 49765, 49899, 50033, 50167, 50299, 50432, 50563,
+    50695, 50826, 50956, 51086, 51215, 51344, 51472,
+];
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn assert_near(actual: Fixed, expected: f32, tolerance: f32) {
+        let diff = actual.to_f32() - expected;
+        assert!(
+            -tolerance < diff && diff < tolerance,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[rstest]
+    #[case(0., 0)]
+    #[case(1., 65536)]
+    #[case(-1., -65536)]
+    #[case(0.5, 32768)]
+    #[case(-2.25, -147_456)]
+    #[case(1e10, i32::MAX)]
+    #[case(f32::NAN, 0)]
+    fn test_from_f32(#[case] value: f32, #[case] expected_bits: i32) {
+        assert_eq!(Fixed::from_f32(value).to_bits(), expected_bits);
+    }
+
+    #[rstest]
+    #[case(1.5, 2.25, 3.75)]
+    #[case(-1.5, 0.25, -1.25)]
+    fn test_add_sub(#[case] lhs: f32, #[case] rhs: f32, #[case] expected: f32) {
+        let (lhs, rhs) = (Fixed::from_f32(lhs), Fixed::from_f32(rhs));
+        assert_eq!(lhs + rhs, Fixed::from_f32(expected));
+        assert_eq!(Fixed::from_f32(expected) - rhs, lhs);
+        let mut sum = lhs;
+        sum += rhs;
+        assert_eq!(sum, Fixed::from_f32(expected));
+    }
+
+    #[rstest]
+    #[case(1.5, 2., 3.)]
+    #[case(-1.5, 0.5, -0.75)]
+    #[case(-3., -4., 12.)]
+    #[case(100., 100., 10000.)]
+    fn test_mul_div(#[case] lhs: f32, #[case] rhs: f32, #[case] expected: f32) {
+        let (lhs, rhs) = (Fixed::from_f32(lhs), Fixed::from_f32(rhs));
+        assert_eq!(lhs * rhs, Fixed::from_f32(expected));
+        assert_eq!(Fixed::from_f32(expected) / rhs, lhs);
+    }
+
+    #[rstest]
+    #[case(1.5, 1., 2., 2., 0.5)]
+    #[case(-1.5, -2., -1., -1., 0.5)]
+    #[case(-1.25, -2., -1., -1., 0.75)]
+    #[case(3., 3., 3., 3., 0.)]
+    fn test_rounding(
+        #[case] value: f32,
+        #[case] floor: f32,
+        #[case] ceil: f32,
+        #[case] round: f32,
+        #[case] fract: f32,
+    ) {
+        let value = Fixed::from_f32(value);
+        assert_eq!(value.floor(), Fixed::from_f32(floor));
+        assert_eq!(value.ceil(), Fixed::from_f32(ceil));
+        assert_eq!(value.round(), Fixed::from_f32(round));
+        assert_eq!(value.fract(), Fixed::from_f32(fract));
+        assert_eq!(value.floor() + value.fract(), value);
+    }
+
+    #[rstest]
+    #[case(0., 0.)]
+    #[case(1., 1.)]
+    #[case(4., 2.)]
+    #[case(2., core::f32::consts::SQRT_2)]
+    #[case(0.25, 0.5)]
+    #[case(30000., 173.205_08)]
+    fn test_sqrt(#[case] value: f32, #[case] expected: f32) {
+        assert_near(Fixed::from_f32(value).sqrt().unwrap(), expected, 0.0001);
+    }
+
+    #[test]
+    fn sqrt_of_negative_is_none() {
+        assert_eq!(Fixed::from_f32(-1.).sqrt(), None);
+    }
+
+    #[rstest]
+    #[case(0., 0., 1.)]
+    #[case(core::f32::consts::FRAC_PI_6, 0.5, 0.866_025_4)]
+    #[case(core::f32::consts::FRAC_PI_2, 1., 0.)]
+    #[case(core::f32::consts::PI, 0., -1.)]
+    #[case(-core::f32::consts::FRAC_PI_2, -1., 0.)]
+    #[case(4., -0.756_802_5, -0.653_643_6)]
+    #[case(-10., 0.544_021_1, -0.839_071_5)]
+    fn test_sin_cos(#[case] angle: f32, #[case] sin: f32, #[case] cos: f32) {
+        let angle = Fixed::from_f32(angle);
+        assert_near(angle.sin(), sin, 0.0005);
+        assert_near(angle.cos(), cos, 0.0005);
+    }
+
+    #[rstest]
+    #[case(0., 1., 0.)]
+    #[case(1., 1., core::f32::consts::FRAC_PI_4)]
+    #[case(1., 0., core::f32::consts::FRAC_PI_2)]
+    #[case(1., -1., 3. * core::f32::consts::FRAC_PI_4)]
+    #[case(0., -1., core::f32::consts::PI)]
+    #[case(-1., -1., -3. * core::f32::consts::FRAC_PI_4)]
+    #[case(-2., 1., -1.107_148_7)]
+    #[case(0., 0., 0.)]
+    fn test_atan2(#[case] y: f32, #[case] x: f32, #[case] expected: f32) {
+        let angle = Fixed::atan2(Fixed::from_f32(y), Fixed::from_f32(x));
+        assert_near(angle, expected, 0.0005);
+    }
+
+    #[test]
+    fn vector_of_fixed() {
+        let vector = Vector::new(Fixed::from_int(3), Fixed::from_int(4));
+        assert_eq!(vector.magnitude(), Fixed::from_int(5));
+        assert_eq!(vector.magnitude_squared(), Fixed::from_int(25));
+        assert_eq!(vector * Fixed::HALF, Vector::new(1.5, 2.).as_vector_fixed());
+        let normal = vector.normalize().unwrap();
+        assert_near(normal.x, 0.6, 0.0001);
+        assert_near(normal.y, 0.8, 0.0001);
+        assert_eq!(Vector::<Fixed>::ZERO.normalize(), None);
+    }
+
+    #[test]
+    fn magnitude_does_not_overflow() {
+        let vector = Vector::new(Fixed::from_int(20000), Fixed::from_int(-20000));
+        assert_near(vector.magnitude(), 28284.271, 0.001);
+    }
+
+    #[rstest]
+    #[case(Fixed::MIN, Fixed::ZERO, Fixed::MAX)]
+    #[case(Fixed::ZERO, Fixed::MIN, Fixed::MAX)]
+    #[case(Fixed::MIN, Fixed::MIN, Fixed::MAX)]
+    #[case(Fixed::MAX, Fixed::MAX, Fixed::MAX)]
+    #[case(Fixed::MAX, Fixed::ZERO, Fixed::MAX)]
+    #[case(Fixed::MIN, Fixed::MAX, Fixed::MAX)]
+    fn magnitude_saturates_at_extremes(
+        #[case] x: Fixed,
+        #[case] y: Fixed,
+        #[case] expected: Fixed,
+    ) {
+        assert_eq!(Vector::new(x, y).magnitude(), expected);
+    }
+
+    #[test]
+    fn mul_and_div_near_the_limits() {
+        assert_eq!(
+            Fixed::from_int(181) * Fixed::from_int(181),
+            Fixed::from_int(32761)
+        );
+        assert_eq!(
+            Fixed::from_int(-16384) / Fixed::HALF,
+            Fixed::from_int(-32768)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn mul_overflow_panics_in_debug() {
+        let _ = Fixed::from_int(300) * Fixed::from_int(300);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn div_overflow_panics_in_debug() {
+        let _ = Fixed::from_int(20000) / Fixed::HALF;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn ceil_overflow_panics_in_debug() {
+        let _ = Fixed::MAX.ceil();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn abs_overflow_panics_in_debug() {
+        let _ = Fixed::MIN.abs();
+    }
+}
@@ -0,0 +1,129 @@
+use crate::vector::Vector;
+
+/// Linear interpolation between `from` and `to`
+///
+/// Returns `from` when `t` is `0` and `to` when `t` is `1`. `t` is not clamped, so values outside of `0..=1`
+/// extrapolate.
+#[must_use]
+pub fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Inverse of [`lerp`]: returns `t` such that `lerp(from, to, t) == value`
+///
+/// Returns `0` if `from` and `to` are equal.
+#[must_use]
+pub fn inverse_lerp(from: f32, to: f32, value: f32) -> f32 {
+    let range = to - from;
+    if range == 0. {
+        0.
+    } else {
+        (value - from) / range
+    }
+}
+
+/// Map `value` from the range `[from_min, from_max]` to the range `[to_min, to_max]`
+#[must_use]
+pub fn remap(value: f32, [from_min, from_max]: [f32; 2], [to_min, to_max]: [f32; 2]) -> f32 {
+    lerp(to_min, to_max, inverse_lerp(from_min, from_max, value))
+}
+
+/// Smooth Hermite interpolation, returning `0` when `x <= edge0` and `1` when `x >= edge1`
+#[must_use]
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, x).clamp(0., 1.);
+    t * t * (3. - 2. * t)
+}
+
+/// Types that can be linearly interpolated
+pub trait Lerp: Copy {
+    /// Returns the linear interpolation between `self` and `to`
+    ///
+    /// Returns `self` when `t` is `0` and `to` when `t` is `1`.
+    #[must_use]
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        lerp(self, to, t)
+    }
+}
+
+/// Rounds to the nearest integer
+impl Lerp for i32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        let delta = (to - self) as f32 * t;
+        let rounded = if delta < 0. { delta - 0.5 } else { delta + 0.5 };
+        self + rounded as i32
+    }
+}
+
+impl Lerp for Vector<f32> {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Vector::lerp(self, to, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0., 10., 0., 0.)]
+    #[case(0., 10., 1., 10.)]
+    #[case(0., 10., 0.5, 5.)]
+    #[case(2., -2., 0.25, 1.)]
+    #[case(0., 10., 2., 20.)]
+    fn test_lerp(#[case] from: f32, #[case] to: f32, #[case] t: f32, #[case] expected: f32) {
+        assert_eq!(lerp(from, to, t), expected);
+        assert_eq!(from.lerp(to, t), expected);
+        assert_eq!(inverse_lerp(from, to, expected), t);
+    }
+
+    #[test]
+    fn inverse_lerp_of_empty_range_is_zero() {
+        assert_eq!(inverse_lerp(1., 1., 5.), 0.);
+    }
+
+    #[rstest]
+    #[case(5., [0., 10.], [0., 1.], 0.5)]
+    #[case(0., [-1., 1.], [10., 20.], 15.)]
+    #[case(2., [0., 1.], [0., -1.], -2.)]
+    fn test_remap(
+        #[case] value: f32,
+        #[case] from: [f32; 2],
+        #[case] to: [f32; 2],
+        #[case] expected: f32,
+    ) {
+        assert_eq!(remap(value, from, to), expected);
+    }
+
+    #[rstest]
+    #[case(-1., 0.)]
+    #[case(0., 0.)]
+    #[case(0.5, 0.5)]
+    #[case(1., 1.)]
+    #[case(2., 1.)]
+    fn test_smoothstep(#[case] x: f32, #[case] expected: f32) {
+        assert_eq!(smoothstep(0., 1., x), expected);
+    }
+
+    #[rstest]
+    #[case(0, 10, 0.5, 5)]
+    #[case(0, 10, 0.34, 3)]
+    #[case(0, 10, 0.36, 4)]
+    #[case(10, 0, 0.36, 6)]
+    fn test_lerp_i32(#[case] from: i32, #[case] to: i32, #[case] t: f32, #[case] expected: i32) {
+        assert_eq!(from.lerp(to, t), expected);
+    }
+
+    #[test]
+    fn test_lerp_vector() {
+        let from = Vector::new(0., 10.);
+        let to = Vector::new(10., 20.);
+        assert_eq!(from.lerp(to, 0.5), Vector::new(5., 15.));
+    }
+}
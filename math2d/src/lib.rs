@@ -1,13 +1,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use angle::Angle;
+pub use angle::{Angle, Degrees, Radians};
+pub use easing::Easing;
+pub use fixed::Fixed;
+pub use interpolation::{inverse_lerp, lerp, remap, smoothstep, Lerp};
+pub use matrix::Matrix;
 #[cfg(feature = "point")]
 pub use point::Point;
+pub use rect::Rect;
+pub use transform::Transform;
 pub use vector::Vector;
 
 mod angle;
+mod easing;
+mod fixed;
+mod interpolation;
+mod matrix;
 #[cfg(feature = "point")]
 mod point;
+mod rect;
+pub mod spline;
+mod transform;
 mod vector;
 
 #[cfg(feature = "std")]
@@ -39,3 +52,63 @@ fn sqrt(v: f32) -> f32 {
 fn sqrt(v: f32) -> f32 {
     libm::sqrtf(v)
 }
+
+#[cfg(feature = "std")]
+fn powf(v: f32, n: f32) -> f32 {
+    v.powf(n)
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn powf(v: f32, n: f32) -> f32 {
+    libm::powf(v, n)
+}
+
+#[cfg(feature = "std")]
+fn abs(v: f32) -> f32 {
+    v.abs()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn abs(v: f32) -> f32 {
+    libm::fabsf(v)
+}
+
+#[cfg(feature = "std")]
+fn floor(v: f32) -> f32 {
+    v.floor()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn floor(v: f32) -> f32 {
+    libm::floorf(v)
+}
+
+#[cfg(feature = "std")]
+fn ceil(v: f32) -> f32 {
+    v.ceil()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn ceil(v: f32) -> f32 {
+    libm::ceilf(v)
+}
+
+#[cfg(feature = "std")]
+fn round(v: f32) -> f32 {
+    v.round()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn round(v: f32) -> f32 {
+    libm::roundf(v)
+}
+
+#[cfg(feature = "std")]
+fn atan2(y: f32, x: f32) -> f32 {
+    y.atan2(x)
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+fn atan2(y: f32, x: f32) -> f32 {
+    libm::atan2f(y, x)
+}
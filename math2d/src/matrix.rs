@@ -0,0 +1,174 @@
+use core::ops::{Mul, MulAssign};
+
+use crate::angle::Angle;
+use crate::vector::Vector;
+
+/// A 2x2 matrix, stored as two column vectors
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix {
+    /// First column (image of the x axis)
+    pub x_axis: Vector,
+    /// Second column (image of the y axis)
+    pub y_axis: Vector,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    /// Matrix with all elements set to zero
+    pub const ZERO: Self = Self::from_cols(Vector::<f32>::ZERO, Vector::<f32>::ZERO);
+
+    /// Identity matrix (does not change the vectors it is multiplied with)
+    pub const IDENTITY: Self = Self::from_cols(Vector::<f32>::X, Vector::<f32>::Y);
+
+    /// Create a matrix from its two columns
+    #[must_use]
+    pub const fn from_cols(x_axis: Vector, y_axis: Vector) -> Self {
+        Self { x_axis, y_axis }
+    }
+
+    /// Create a matrix scaling each axis by the components of `scale`
+    #[must_use]
+    pub const fn from_scale(scale: Vector) -> Self {
+        Self::from_cols(Vector::new(scale.x, 0.), Vector::new(0., scale.y))
+    }
+
+    /// Create a rotation matrix
+    #[must_use]
+    pub fn from_angle(angle: impl Angle) -> Self {
+        let (cos, sin) = (angle.cos(), angle.sin());
+        Self::from_cols(Vector::new(cos, sin), Vector::new(-sin, cos))
+    }
+
+    /// Returns the determinant of the matrix
+    #[must_use]
+    pub fn determinant(self) -> f32 {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+
+    /// Returns the transposed matrix
+    #[must_use]
+    pub fn transpose(self) -> Self {
+        Self::from_cols(
+            Vector::new(self.x_axis.x, self.y_axis.x),
+            Vector::new(self.x_axis.y, self.y_axis.y),
+        )
+    }
+
+    /// Returns the inverse of the matrix if possible
+    ///
+    /// If the matrix cannot be inverted, returns `None` (e.g. if the determinant is zero)
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        let recip = self.determinant().recip();
+        if !recip.is_finite() {
+            return None;
+        }
+        Some(Self::from_cols(
+            Vector::new(self.y_axis.y, -self.x_axis.y) * recip,
+            Vector::new(-self.y_axis.x, self.x_axis.x) * recip,
+        ))
+    }
+}
+
+impl Mul<Vector> for Matrix {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Self::Output {
+        self.x_axis * rhs.x + self.y_axis * rhs.y
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_cols(self * rhs.x_axis, self * rhs.y_axis)
+    }
+}
+
+impl MulAssign for Matrix {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<f32> for Matrix {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::from_cols(self.x_axis * rhs, self.y_axis * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    use crate::angle::Degrees;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Matrix::IDENTITY, Vector::new(2., 3.), Vector::new(2., 3.))]
+    #[case(Matrix::ZERO, Vector::new(2., 3.), Vector::<f32>::ZERO)]
+    #[case(Matrix::from_scale(Vector::new(2., -1.)), Vector::new(2., 3.), Vector::new(4., -3.))]
+    #[case(Matrix::from_cols(Vector::<f32>::Y, Vector::<f32>::X), Vector::new(2., 3.), Vector::new(3., 2.))]
+    fn test_mul_vector(#[case] matrix: Matrix, #[case] vector: Vector, #[case] expected: Vector) {
+        assert_eq!(matrix * vector, expected);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn rotation_matrix_rotates_vectors() {
+        let vector = Vector::new(1., 2.);
+        let expected = vector.rotate(Degrees(30.));
+        let actual = Matrix::from_angle(Degrees(30.)) * vector;
+        assert!((actual.x - expected.x).abs() < 0.000001);
+        assert!((actual.y - expected.y).abs() < 0.000001);
+    }
+
+    #[test]
+    fn mul_matrix_composes_transformations() {
+        let a = Matrix::from_cols(Vector::new(1., 2.), Vector::new(3., 4.));
+        let b = Matrix::from_cols(Vector::new(-1., 0.5), Vector::new(2., 1.));
+        let vector = Vector::new(5., 7.);
+        assert_eq!((a * b) * vector, a * (b * vector));
+    }
+
+    #[rstest]
+    #[case(Matrix::IDENTITY)]
+    #[case(Matrix::from_scale(Vector::new(2., 4.)))]
+    #[case(Matrix::from_cols(Vector::new(1., 2.), Vector::new(3., 4.)))]
+    fn inverse_cancels_matrix(#[case] matrix: Matrix) {
+        let product = matrix * matrix.inverse().unwrap();
+        assert!((product.x_axis - Vector::<f32>::X).magnitude_squared() < 0.000001);
+        assert!((product.y_axis - Vector::<f32>::Y).magnitude_squared() < 0.000001);
+    }
+
+    #[rstest]
+    fn inverse_returns_none_if_cannot_be_inverted(
+        #[values(
+            Matrix::ZERO,
+            Matrix::from_cols(Vector::new(1., 2.), Vector::new(2., 4.))
+        )]
+        matrix: Matrix,
+    ) {
+        assert_eq!(matrix.inverse(), None);
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let matrix = Matrix::from_cols(Vector::new(1., 2.), Vector::new(3., 4.));
+        assert_eq!(
+            matrix.transpose(),
+            Matrix::from_cols(Vector::new(1., 3.), Vector::new(2., 4.))
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use core::ops::{Add, Div, Sub};
+
+use crate::vector::Vector;
+
+/// An axis-aligned rectangle
+///
+/// The rectangle contains the points `p` such that `min <= p < max` (on both axes).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Rect<T = f32> {
+    /// Corner with the smallest coordinates (top-left, with the y axis pointing down)
+    pub min: Vector<T>,
+    /// Corner with the largest coordinates (bottom-right, with the y axis pointing down)
+    pub max: Vector<T>,
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// Create a rectangle from two opposite corners (in any order)
+    #[must_use]
+    pub fn from_min_max(min: impl Into<Vector<T>>, max: impl Into<Vector<T>>) -> Self {
+        let (a, b) = (min.into(), max.into());
+        Self {
+            min: Vector::new(partial_min(a.x, b.x), partial_min(a.y, b.y)),
+            max: Vector::new(partial_max(a.x, b.x), partial_max(a.y, b.y)),
+        }
+    }
+
+    /// Returns true if `point` is inside the rectangle
+    #[must_use]
+    pub fn contains(self, point: impl Into<Vector<T>>) -> bool {
+        let point = point.into();
+        self.min.x <= point.x
+            && point.x < self.max.x
+            && self.min.y <= point.y
+            && point.y < self.max.y
+    }
+
+    /// Returns true if `other` is entirely inside `self`
+    #[must_use]
+    pub fn contains_rect(self, other: Self) -> bool {
+        self.min.x <= other.min.x
+            && other.max.x <= self.max.x
+            && self.min.y <= other.min.y
+            && other.max.y <= self.max.y
+    }
+
+    /// Returns true if `self` and `other` overlap
+    ///
+    /// Rectangles that only touch each other by an edge or a corner do not overlap
+    #[must_use]
+    pub fn intersects(self, other: Self) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+
+    /// Returns the overlapping part of `self` and `other`
+    ///
+    /// Returns `None` if the rectangles do not overlap (see [`Self::intersects`])
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Self {
+            min: Vector::new(
+                partial_max(self.min.x, other.min.x),
+                partial_max(self.min.y, other.min.y),
+            ),
+            max: Vector::new(
+                partial_min(self.max.x, other.max.x),
+                partial_min(self.max.y, other.max.y),
+            ),
+        })
+    }
+
+    /// Returns the smallest rectangle containing both `self` and `other`
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min: Vector::new(
+                partial_min(self.min.x, other.min.x),
+                partial_min(self.min.y, other.min.y),
+            ),
+            max: Vector::new(
+                partial_max(self.max.x, other.max.x),
+                partial_max(self.max.y, other.max.y),
+            ),
+        }
+    }
+
+    /// Split the rectangle into a left part (before `x`) and right part (after `x`)
+    ///
+    /// Returns `None` if `x` is not strictly between the left and right edges
+    #[must_use]
+    pub fn split_at_x(self, x: T) -> Option<(Self, Self)> {
+        if x <= self.min.x || self.max.x <= x {
+            return None;
+        }
+        let left = Self {
+            min: self.min,
+            max: Vector::new(x, self.max.y),
+        };
+        let right = Self {
+            min: Vector::new(x, self.min.y),
+            max: self.max,
+        };
+        Some((left, right))
+    }
+
+    /// Split the rectangle into a top part (before `y`) and bottom part (after `y`)
+    ///
+    /// Returns `None` if `y` is not strictly between the top and bottom edges
+    #[must_use]
+    pub fn split_at_y(self, y: T) -> Option<(Self, Self)> {
+        if y <= self.min.y || self.max.y <= y {
+            return None;
+        }
+        let top = Self {
+            min: self.min,
+            max: Vector::new(self.max.x, y),
+        };
+        let bottom = Self {
+            min: Vector::new(self.min.x, y),
+            max: self.max,
+        };
+        Some((top, bottom))
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Create a rectangle from its top-left corner and its size
+    #[must_use]
+    pub fn from_min_size(min: impl Into<Vector<T>>, size: impl Into<Vector<T>>) -> Self {
+        let min = min.into();
+        Self::from_min_max(min, min + size.into())
+    }
+
+    #[must_use]
+    pub fn size(self) -> Vector<T> {
+        self.max - self.min
+    }
+
+    #[must_use]
+    pub fn width(self) -> T {
+        self.max.x - self.min.x
+    }
+
+    #[must_use]
+    pub fn height(self) -> T {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the rectangle moved by `offset`
+    #[must_use]
+    pub fn translate(self, offset: impl Into<Vector<T>>) -> Self {
+        let offset = offset.into();
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    /// Create a rectangle from its center and its size
+    #[must_use]
+    pub fn from_center_size(center: impl Into<Vector<T>>, size: impl Into<Vector<T>>) -> Self {
+        let center = center.into();
+        let half_size = size.into() / T::from(2);
+        Self::from_min_max(center - half_size, center + half_size)
+    }
+
+    #[must_use]
+    pub fn center(self) -> Vector<T> {
+        self.min + self.size() / T::from(2)
+    }
+}
+
+impl Rect<f32> {
+    /// cast into a [`Rect<i32>`]
+    ///
+    /// Coordinates are truncated toward zero
+    #[must_use]
+    pub fn as_rect_i32(self) -> Rect<i32> {
+        Rect {
+            min: self.min.as_vector_i32(),
+            max: self.max.as_vector_i32(),
+        }
+    }
+}
+
+impl Rect<i32> {
+    /// cast into a [`Rect<f32>`]
+    #[must_use]
+    pub fn as_rect_f32(self) -> Rect<f32> {
+        Rect {
+            min: self.min.as_vector_f32(),
+            max: self.max.as_vector_f32(),
+        }
+    }
+}
+
+fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn rect(min: [i32; 2], max: [i32; 2]) -> Rect<i32> {
+        Rect::from_min_max(min, max)
+    }
+
+    #[test]
+    fn corners_are_ordered() {
+        assert_eq!(rect([4, 1], [2, 3]), rect([2, 1], [4, 3]));
+        assert_eq!(rect([4, 1], [2, 3]).min, Vector::new(2, 1));
+    }
+
+    #[test]
+    fn test_size() {
+        let rect = Rect::from_min_size([1, 2], [3, 4]);
+        assert_eq!(rect.max, Vector::new(4, 6));
+        assert_eq!(rect.size(), Vector::new(3, 4));
+        assert_eq!(rect.width(), 3);
+        assert_eq!(rect.height(), 4);
+    }
+
+    #[test]
+    fn test_center() {
+        let rect = Rect::from_center_size([1., 2.], [4., 2.]);
+        assert_eq!(rect, Rect::from_min_max([-1., 1.], [3., 3.]));
+        assert_eq!(rect.center(), Vector::new(1., 2.));
+    }
+
+    #[rstest]
+    #[case([0, 0], true)]
+    #[case([1, 1], true)]
+    #[case([2, 1], false)]
+    #[case([1, 2], false)]
+    #[case([-1, 0], false)]
+    fn test_contains(#[case] point: [i32; 2], #[case] expected: bool) {
+        assert_eq!(rect([0, 0], [2, 2]).contains(point), expected);
+    }
+
+    #[rstest]
+    #[case(rect([0, 0], [2, 2]), true)]
+    #[case(rect([1, 1], [2, 2]), true)]
+    #[case(rect([1, 1], [3, 2]), false)]
+    #[case(rect([-1, 0], [1, 1]), false)]
+    fn test_contains_rect(#[case] other: Rect<i32>, #[case] expected: bool) {
+        assert_eq!(rect([0, 0], [2, 2]).contains_rect(other), expected);
+    }
+
+    #[rstest]
+    #[case(rect([1, 1], [3, 3]), Some(rect([1, 1], [2, 2])))]
+    #[case(rect([-1, -1], [3, 3]), Some(rect([0, 0], [2, 2])))]
+    #[case(rect([2, 0], [3, 2]), None)]
+    #[case(rect([0, 2], [2, 3]), None)]
+    #[case(rect([5, 5], [6, 6]), None)]
+    fn test_intersection(#[case] other: Rect<i32>, #[case] expected: Option<Rect<i32>>) {
+        let rect = rect([0, 0], [2, 2]);
+        assert_eq!(rect.intersects(other), expected.is_some());
+        assert_eq!(other.intersects(rect), expected.is_some());
+        assert_eq!(rect.intersection(other), expected);
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(
+            rect([0, 0], [1, 1]).union(rect([2, -1], [3, 0])),
+            rect([0, -1], [3, 1])
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let rect = rect([0, 0], [4, 2]);
+        assert_eq!(
+            rect.split_at_x(1),
+            Some((self::rect([0, 0], [1, 2]), self::rect([1, 0], [4, 2])))
+        );
+        assert_eq!(
+            rect.split_at_y(1),
+            Some((self::rect([0, 0], [4, 1]), self::rect([0, 1], [4, 2])))
+        );
+        assert_eq!(rect.split_at_x(0), None);
+        assert_eq!(rect.split_at_x(4), None);
+        assert_eq!(rect.split_at_y(3), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(rect([0, 0], [1, 1]).translate([2, 3]), rect([2, 3], [3, 4]));
+    }
+
+    #[test]
+    fn test_cast() {
+        let rect = rect([1, 2], [3, 4]);
+        assert_eq!(rect.as_rect_f32().as_rect_i32(), rect);
+    }
+}
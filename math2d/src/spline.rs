@@ -0,0 +1,135 @@
+//! Evaluation of bezier curves and catmull-rom splines
+
+use crate::vector::Vector;
+
+/// Evaluate the quadratic bezier curve from `p0` to `p2` with control point `p1` at `t` (in `0..=1`)
+#[must_use]
+pub fn quadratic_bezier(p0: Vector, p1: Vector, p2: Vector, t: f32) -> Vector {
+    p0.lerp(p1, t).lerp(p1.lerp(p2, t), t)
+}
+
+/// Evaluate the cubic bezier curve from `p0` to `p3` with control points `p1` and `p2` at `t` (in `0..=1`)
+#[must_use]
+pub fn cubic_bezier(p0: Vector, p1: Vector, p2: Vector, p3: Vector, t: f32) -> Vector {
+    quadratic_bezier(p0, p1, p2, t).lerp(quadratic_bezier(p1, p2, p3, t), t)
+}
+
+/// Evaluate the (uniform) catmull-rom segment between `p1` and `p2` at `t` (in `0..=1`)
+///
+/// `p0` and `p3` are the points before and after the segment, and only affect its tangents.
+#[must_use]
+pub fn catmull_rom(p0: Vector, p1: Vector, p2: Vector, p3: Vector, t: f32) -> Vector {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.
+        + (p2 - p0) * t
+        + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * t2
+        + (p1 * 3. - p0 - p2 * 3. + p3) * t3)
+        * 0.5
+}
+
+/// Evaluate the catmull-rom spline passing through all the `points` at `t` (in `0..=1`)
+///
+/// `t` is distributed uniformly over the segments (not over the length), and is clamped in `0..=1`.
+///
+/// Returns `None` if `points` is empty.
+#[must_use]
+pub fn catmull_rom_path(points: &[Vector], t: f32) -> Option<Vector> {
+    let last = points.len().checked_sub(1)?;
+    if last == 0 {
+        return Some(points[0]);
+    }
+    let position = t.clamp(0., 1.) * last as f32;
+    let segment = (position as usize).min(last - 1);
+    let get = |i: isize| points[i.clamp(0, last as isize) as usize];
+    let i = segment as isize;
+    Some(catmull_rom(
+        get(i - 1),
+        get(i),
+        get(i + 1),
+        get(i + 2),
+        position - segment as f32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn assert_near(actual: Vector, expected: Vector) {
+        assert!(
+            (actual - expected).magnitude_squared() < 0.00001,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[rstest]
+    #[case(0., Vector::new(0., 0.))]
+    #[case(0.5, Vector::new(1., 1.))]
+    #[case(1., Vector::new(2., 0.))]
+    fn test_quadratic_bezier(#[case] t: f32, #[case] expected: Vector) {
+        let result = quadratic_bezier(
+            Vector::new(0., 0.),
+            Vector::new(1., 2.),
+            Vector::new(2., 0.),
+            t,
+        );
+        assert_near(result, expected);
+    }
+
+    #[rstest]
+    #[case(0., Vector::new(0., 0.))]
+    #[case(0.5, Vector::new(1.5, 1.5))]
+    #[case(1., Vector::new(3., 0.))]
+    fn test_cubic_bezier(#[case] t: f32, #[case] expected: Vector) {
+        let result = cubic_bezier(
+            Vector::new(0., 0.),
+            Vector::new(0., 2.),
+            Vector::new(3., 2.),
+            Vector::new(3., 0.),
+            t,
+        );
+        assert_near(result, expected);
+    }
+
+    #[rstest]
+    #[case(0., Vector::new(1., 0.))]
+    #[case(0.5, Vector::new(1.5, 0.))]
+    #[case(1., Vector::new(2., 0.))]
+    fn catmull_rom_on_a_line_is_linear(#[case] t: f32, #[case] expected: Vector) {
+        let result = catmull_rom(
+            Vector::new(0., 0.),
+            Vector::new(1., 0.),
+            Vector::new(2., 0.),
+            Vector::new(3., 0.),
+            t,
+        );
+        assert_near(result, expected);
+    }
+
+    #[rstest]
+    #[case(0., Vector::new(0., 0.))]
+    #[case(0.5, Vector::new(1., 1.))]
+    #[case(1., Vector::new(2., 0.))]
+    fn catmull_rom_path_passes_through_all_points(#[case] t: f32, #[case] expected: Vector) {
+        let points = [
+            Vector::new(0., 0.),
+            Vector::new(1., 1.),
+            Vector::new(2., 0.),
+        ];
+        assert_near(catmull_rom_path(&points, t).unwrap(), expected);
+    }
+
+    #[test]
+    fn catmull_rom_path_of_single_point_is_the_point() {
+        let point = Vector::new(1., 2.);
+        assert_eq!(catmull_rom_path(&[point], 0.7), Some(point));
+    }
+
+    #[test]
+    fn catmull_rom_path_of_no_point_is_none() {
+        assert_eq!(catmull_rom_path(&[], 0.5), None);
+    }
+}
@@ -0,0 +1,212 @@
+use core::ops::{Mul, MulAssign};
+
+use crate::angle::Angle;
+use crate::matrix::Matrix;
+#[cfg(feature = "point")]
+use crate::point::Point;
+use crate::vector::Vector;
+
+/// A 2d affine transformation (combination of translation, rotation, scale and shear)
+///
+/// Transformations are composed with `*`, where `a * b` first applies `b` and then `a`.
+/// [`Transform::then`] can be used to compose them in reading order instead.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    /// Linear part of the transformation
+    pub matrix: Matrix,
+    /// Translation applied after the linear part
+    pub translation: Vector,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// Transformation that does nothing
+    pub const IDENTITY: Self = Self::from_matrix_translation(Matrix::IDENTITY, Vector::<f32>::ZERO);
+
+    #[must_use]
+    pub const fn from_matrix_translation(matrix: Matrix, translation: Vector) -> Self {
+        Self {
+            matrix,
+            translation,
+        }
+    }
+
+    #[must_use]
+    pub const fn from_translation(translation: Vector) -> Self {
+        Self::from_matrix_translation(Matrix::IDENTITY, translation)
+    }
+
+    /// Create a transformation scaling each axis by the components of `scale`
+    #[must_use]
+    pub const fn from_scale(scale: Vector) -> Self {
+        Self::from_matrix_translation(Matrix::from_scale(scale), Vector::<f32>::ZERO)
+    }
+
+    /// Create a transformation scaling both axes by `scale`
+    #[must_use]
+    pub const fn from_uniform_scale(scale: f32) -> Self {
+        Self::from_scale(Vector::new(scale, scale))
+    }
+
+    /// Create a transformation rotating around the origin
+    #[must_use]
+    pub fn from_angle(angle: impl Angle) -> Self {
+        Self::from_matrix_translation(Matrix::from_angle(angle), Vector::<f32>::ZERO)
+    }
+
+    /// Create a transformation that scales, then rotates, then translates
+    #[must_use]
+    pub fn from_scale_angle_translation(
+        scale: Vector,
+        angle: impl Angle,
+        translation: Vector,
+    ) -> Self {
+        Self::from_matrix_translation(
+            Matrix::from_angle(angle) * Matrix::from_scale(scale),
+            translation,
+        )
+    }
+
+    /// Returns a transformation that applies `self` and then `next`
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        next * self
+    }
+
+    /// Returns the inverse transformation if possible
+    ///
+    /// If the transformation cannot be inverted, returns `None` (e.g. if it has a scale of zero)
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        let matrix = self.matrix.inverse()?;
+        Some(Self::from_matrix_translation(
+            matrix,
+            -(matrix * self.translation),
+        ))
+    }
+
+    /// Transform a position (the translation is applied)
+    #[must_use]
+    pub fn transform_position(self, position: Vector) -> Vector {
+        self.matrix * position + self.translation
+    }
+
+    /// Transform a displacement or direction (the translation is ignored)
+    #[must_use]
+    pub fn transform_vector(self, vector: Vector) -> Vector {
+        self.matrix * vector
+    }
+
+    /// Transform a point (the translation is applied)
+    #[must_use]
+    #[cfg(feature = "point")]
+    pub fn transform_point(self, point: Point) -> Point {
+        self.transform_position(point.into()).into()
+    }
+}
+
+impl Mul for Transform {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_matrix_translation(
+            self.matrix * rhs.matrix,
+            self.transform_position(rhs.translation),
+        )
+    }
+}
+
+impl MulAssign for Transform {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(feature = "point")]
+impl Mul<Point> for Transform {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    use crate::angle::Degrees;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Transform::IDENTITY, Vector::new(2., 3.), Vector::new(2., 3.))]
+    #[case(Transform::from_translation(Vector::new(1., -1.)), Vector::new(2., 3.), Vector::new(3., 2.))]
+    #[case(
+        Transform::from_uniform_scale(16.),
+        Vector::new(2., 3.),
+        Vector::new(32., 48.)
+    )]
+    #[case(
+        Transform::from_uniform_scale(2.).then(Transform::from_translation(Vector::new(1., 0.))),
+        Vector::new(2., 3.),
+        Vector::new(5., 6.)
+    )]
+    #[case(
+        Transform::from_translation(Vector::new(1., 0.)).then(Transform::from_uniform_scale(2.)),
+        Vector::new(2., 3.),
+        Vector::new(6., 6.)
+    )]
+    fn test_transform_position(
+        #[case] transform: Transform,
+        #[case] position: Vector,
+        #[case] expected: Vector,
+    ) {
+        assert_eq!(transform.transform_position(position), expected);
+    }
+
+    #[test]
+    fn transform_vector_ignores_translation() {
+        let transform = Transform::from_translation(Vector::new(1., 2.))
+            .then(Transform::from_scale(Vector::new(2., 3.)));
+        assert_eq!(
+            transform.transform_vector(Vector::new(1., 1.)),
+            Vector::new(2., 3.)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn inverse_cancels_transform() {
+        let transform = Transform::from_scale_angle_translation(
+            Vector::new(2., 3.),
+            Degrees(30.),
+            Vector::new(-4., 5.),
+        );
+        let position = Vector::new(7., -1.);
+        let result = transform
+            .inverse()
+            .unwrap()
+            .transform_position(transform.transform_position(position));
+        assert!((result - position).magnitude_squared() < 0.00001);
+    }
+
+    #[test]
+    fn inverse_returns_none_if_cannot_be_inverted() {
+        assert_eq!(Transform::from_uniform_scale(0.).inverse(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "point")]
+    fn transform_point_applies_translation() {
+        let transform = Transform::from_translation(Vector::new(1., 2.));
+        assert_eq!(transform * Point::new(1., 1.), Point::new(2., 3.));
+    }
+}
@@ -1,6 +1,8 @@
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::angle::Angle;
+#[cfg(any(feature = "std", feature = "libm"))]
+use crate::angle::Radians;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
@@ -39,6 +41,34 @@ where
     }
 }
 
+impl<T> Vector<T>
+where
+    T: Copy + Mul<Output = T> + Sub<Output = T>,
+{
+    /// Returns the perp-dot product (aka 2d cross product) of the two vectors
+    ///
+    /// This is positive if `rhs` is clockwise from `self` (with the y axis pointing down), negative if it is
+    /// counter-clockwise, and zero if the vectors are parallel.
+    #[must_use]
+    pub fn cross(self, rhs: Self) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+}
+
+impl<T> Vector<T>
+where
+    T: Neg<Output = T>,
+{
+    /// Returns the vector rotated by 90 degrees (from the x axis toward the y axis)
+    #[must_use]
+    pub fn perp(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+}
+
 impl Vector<f32> {
     /// Zero vector
     pub const ZERO: Self = Self::new(0., 0.);
@@ -83,7 +113,119 @@ impl Vector<f32> {
         }
     }
 
+    /// Returns the linear interpolation between `self` and `to`
+    ///
+    /// Returns `self` when `t` is `0` and `to` when `t` is `1`.
+    #[must_use]
+    pub fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+
+    /// Returns the projection of `self` onto `other`
+    ///
+    /// Returns [`Self::ZERO`] if `other` has a magnitude of zero
+    #[must_use]
+    pub fn project_onto(self, other: Self) -> Self {
+        let magnitude_squared = other.magnitude_squared();
+        if magnitude_squared == 0. {
+            return Self::ZERO;
+        }
+        other * (self.dot(other) / magnitude_squared)
+    }
+
+    /// Returns the reflection of `self` against a surface of the given (normalized) `normal`
+    #[must_use]
+    pub fn reflect(self, normal: Self) -> Self {
+        self - normal * (2. * self.dot(normal))
+    }
+
+    /// Returns the component-wise minimum of `self` and `other`
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Returns the vector with the absolute value of each component
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn abs(self) -> Self {
+        Self::new(crate::abs(self.x), crate::abs(self.y))
+    }
+
+    /// Returns the vector with each component rounded down
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn floor(self) -> Self {
+        Self::new(crate::floor(self.x), crate::floor(self.y))
+    }
+
+    /// Returns the vector with each component rounded up
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn ceil(self) -> Self {
+        Self::new(crate::ceil(self.x), crate::ceil(self.y))
+    }
+
+    /// Returns the vector with each component rounded to the nearest integer (half-way cases away from zero)
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn round(self) -> Self {
+        Self::new(crate::round(self.x), crate::round(self.y))
+    }
+
+    /// Returns the vector with the same direction and a magnitude clamped between `min` and `max`
+    ///
+    /// A vector of magnitude zero is returned unchanged
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn clamp_length(self, min: f32, max: f32) -> Self {
+        let magnitude = self.magnitude();
+        if magnitude == 0. {
+            self
+        } else if magnitude < min {
+            self * (min / magnitude)
+        } else if magnitude > max {
+            self * (max / magnitude)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the vector with the same direction and a magnitude of at most `max`
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn clamp_length_max(self, max: f32) -> Self {
+        self.clamp_length(0., max)
+    }
+
+    /// Returns the angle of the vector, relative to the x axis
+    ///
+    /// The result is in the range `-PI..=PI`
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn angle(self) -> Radians {
+        Radians(crate::atan2(self.y, self.x))
+    }
+
+    /// Returns the signed angle to rotate `self` by in order to get the direction of `other`
+    ///
+    /// The result is in the range `-PI..=PI`
+    #[must_use]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn angle_to(self, other: Self) -> Radians {
+        Radians(crate::atan2(self.cross(other), self.dot(other)))
+    }
+
     /// cast into a [`Vector<i32>`]
+    ///
+    /// Components are truncated toward zero, consider calling [`Self::round`] first to get the nearest
+    /// integer instead.
     #[must_use]
     pub fn as_vector_i32(self) -> Vector<i32> {
         Vector {
@@ -110,6 +252,24 @@ impl Vector<i32> {
     /// Unit vector pointing in direction of the y axis
     pub const Y: Self = Self::new(0, 1);
 
+    /// Returns the component-wise minimum of `self` and `other`
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Returns the vector with the absolute value of each component
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+
     /// cast into a [`Vector<f32>`]
     #[must_use]
     pub fn as_vector_f32(self) -> Vector<f32> {
@@ -156,6 +316,13 @@ where
     }
 }
 
+#[cfg(any(feature = "std", feature = "libm"))]
+impl From<Vector<f32>> for Radians {
+    fn from(vector: Vector<f32>) -> Self {
+        vector.angle()
+    }
+}
+
 impl<T> AddAssign for Vector<T>
 where
     T: AddAssign,
@@ -343,4 +510,148 @@ mod tests {
     ) {
         assert_eq!(vector.normalize(), None);
     }
+
+    #[rstest]
+    #[case(Vector::<i32>::X, Vector::<i32>::Y, 1)]
+    #[case(Vector::<i32>::Y, Vector::<i32>::X, -1)]
+    #[case(Vector::new(2, 4), Vector::new(1, 2), 0)]
+    fn test_cross(#[case] lhs: Vector<i32>, #[case] rhs: Vector<i32>, #[case] expected: i32) {
+        assert_eq!(lhs.cross(rhs), expected);
+    }
+
+    #[rstest]
+    #[case(Vector::<i32>::X, Vector::<i32>::Y)]
+    #[case(Vector::<i32>::Y, -Vector::<i32>::X)]
+    #[case(Vector::new(2, 3), Vector::new(-3, 2))]
+    fn test_perp(#[case] vector: Vector<i32>, #[case] expected: Vector<i32>) {
+        assert_eq!(vector.perp(), expected);
+        assert_eq!(vector.dot(vector.perp()), 0);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let from = Vector::new(0., 10.);
+        let to = Vector::new(10., 20.);
+        assert_eq!(from.lerp(to, 0.), from);
+        assert_eq!(from.lerp(to, 1.), to);
+        assert_eq!(from.lerp(to, 0.25), Vector::new(2.5, 12.5));
+    }
+
+    #[rstest]
+    #[case(Vector::new(2., 3.), Vector::<f32>::X, Vector::new(2., 0.))]
+    #[case(Vector::new(2., 3.), Vector::new(0., -5.), Vector::new(0., 3.))]
+    #[case(Vector::new(2., 3.), Vector::<f32>::ZERO, Vector::<f32>::ZERO)]
+    fn test_project_onto(#[case] vector: Vector, #[case] other: Vector, #[case] expected: Vector) {
+        assert_eq!(vector.project_onto(other), expected);
+    }
+
+    #[rstest]
+    #[case(Vector::new(1., 1.), -Vector::<f32>::Y, Vector::new(1., -1.))]
+    #[case(Vector::new(1., 1.), Vector::<f32>::X, Vector::new(-1., 1.))]
+    fn test_reflect(#[case] vector: Vector, #[case] normal: Vector, #[case] expected: Vector) {
+        assert_eq!(vector.reflect(normal), expected);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let a = Vector::new(1., 4.);
+        let b = Vector::new(3., 2.);
+        assert_eq!(a.min(b), Vector::new(1., 2.));
+        assert_eq!(a.max(b), Vector::new(3., 4.));
+        let a = Vector::new(1, 4);
+        let b = Vector::new(3, 2);
+        assert_eq!(a.min(b), Vector::new(1, 2));
+        assert_eq!(a.max(b), Vector::new(3, 4));
+    }
+
+    #[test]
+    fn test_abs_i32() {
+        assert_eq!(Vector::new(-1, 2).abs(), Vector::new(1, 2));
+    }
+
+    #[rstest]
+    #[case(Vector::new(-1.5, 2.5), Vector::new(1.5, 2.5))]
+    #[case(Vector::new(0., -0.), Vector::new(0., 0.))]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_abs(#[case] vector: Vector, #[case] expected: Vector) {
+        assert_eq!(vector.abs(), expected);
+    }
+
+    #[rstest]
+    #[case(Vector::new(1.4, -1.4), [1., -2.], [2., -1.], [1., -1.])]
+    #[case(Vector::new(1.5, -1.5), [1., -2.], [2., -1.], [2., -2.])]
+    #[case(Vector::new(2., 0.), [2., 0.], [2., 0.], [2., 0.])]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_rounding(
+        #[case] vector: Vector,
+        #[case] floor: [f32; 2],
+        #[case] ceil: [f32; 2],
+        #[case] round: [f32; 2],
+    ) {
+        assert_eq!(vector.floor(), floor.into());
+        assert_eq!(vector.ceil(), ceil.into());
+        assert_eq!(vector.round(), round.into());
+    }
+
+    #[rstest]
+    #[case(Vector::new(3., 4.), 1., 10., Vector::new(3., 4.))]
+    #[case(Vector::new(3., 4.), 1., 2.5, Vector::new(1.5, 2.))]
+    #[case(Vector::new(3., 4.), 10., 20., Vector::new(6., 8.))]
+    #[case(Vector::<f32>::ZERO, 1., 2., Vector::<f32>::ZERO)]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_clamp_length(
+        #[case] vector: Vector,
+        #[case] min: f32,
+        #[case] max: f32,
+        #[case] expected: Vector,
+    ) {
+        let result = vector.clamp_length(min, max);
+        assert!((result - expected).magnitude_squared() < 0.000001);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_clamp_length_max() {
+        assert_eq!(
+            Vector::new(0., 3.).clamp_length_max(2.),
+            Vector::new(0., 2.)
+        );
+        assert_eq!(
+            Vector::new(0., 1.).clamp_length_max(2.),
+            Vector::new(0., 1.)
+        );
+    }
+
+    #[rstest]
+    #[case(Vector::<f32>::X, Degrees(0.))]
+    #[case(Vector::<f32>::Y, Degrees(90.))]
+    #[case(-Vector::<f32>::Y, Degrees(-90.))]
+    #[case(Vector::new(-1., 0.), Degrees(180.))]
+    #[case(Vector::new(1., 1.), Degrees(45.))]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_angle(#[case] vector: Vector, #[case] expected: Degrees) {
+        let Degrees(angle) = vector.angle().into();
+        assert!(
+            (angle - expected.0).abs() < 0.0001,
+            "{angle} != {expected:?}"
+        );
+        let Radians(angle) = vector.into();
+        assert!((angle - expected.0.to_radians()).abs() < 0.0001);
+    }
+
+    #[rstest]
+    #[case(Vector::<f32>::X, Vector::<f32>::Y, Degrees(90.))]
+    #[case(Vector::<f32>::Y, Vector::<f32>::X, Degrees(-90.))]
+    #[case(Vector::new(0., 2.), Vector::new(-1., 1.), Degrees(45.))]
+    #[case(Vector::new(1., 1.), Vector::new(2., 2.), Degrees(0.))]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_angle_to(#[case] from: Vector, #[case] to: Vector, #[case] expected: Degrees) {
+        let Degrees(angle) = from.angle_to(to).into();
+        assert!(
+            (angle - expected.0).abs() < 0.0001,
+            "{angle} != {expected:?}"
+        );
+        let rotated = from.rotate(from.angle_to(to)).normalize().unwrap();
+        assert!((rotated - to.normalize().unwrap()).magnitude_squared() < 0.000001);
+    }
 }
@@ -1,26 +1,163 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use core::time::Duration;
 
-use timer::Timer;
+use anyhow::anyhow;
+use serde::Deserialize;
 
+/// Description of the animation clips available in a sprite sheet
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sheet {
+    /// Number of frames (columns) in the sprite sheet image
+    pub columns: usize,
+    /// Origin of the sprite, relative to the top-left of a frame
+    #[serde(default)]
+    pub pivot: Option<[i32; 2]>,
+    clips: BTreeMap<String, Clip>,
+}
+
+impl Sheet {
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let sheet: Self = serde_json::from_str(raw)
+            .map_err(|err| anyhow!("failed to deserialize animation sheet: {err}"))?;
+        for (name, clip) in &sheet.clips {
+            if clip.frames.is_empty() {
+                return Err(anyhow!("animation clip '{name}' has no frame"));
+            }
+            for frame in &clip.frames {
+                if frame.index >= sheet.columns {
+                    return Err(anyhow!(
+                        "animation clip '{name}' refers to frame {} but the sheet only has {} columns",
+                        frame.index,
+                        sheet.columns
+                    ));
+                }
+                if frame.duration_ms == 0 {
+                    return Err(anyhow!(
+                        "animation clip '{name}' has a frame without duration"
+                    ));
+                }
+            }
+        }
+        Ok(sheet)
+    }
+
+    pub fn clip(&self, name: &str) -> anyhow::Result<Clip> {
+        self.clips
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("no animation clip named '{name}'"))
+    }
+}
+
+/// Sequence of frames of a sprite sheet
+#[derive(Debug, Clone, Deserialize)]
+pub struct Clip {
+    frames: Vec<Frame>,
+    #[serde(default)]
+    repeat: Repeat,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+struct Frame {
+    /// Column of the frame in the sprite sheet
+    index: usize,
+    duration_ms: u64,
+}
+
+impl Frame {
+    fn duration(self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+}
+
+/// What a clip does after its last frame
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repeat {
+    /// Start over from the first frame
+    #[default]
+    Loop,
+    /// Stay on the last frame
+    Once,
+    /// Play backward to the first frame, then forward again, and so on
+    PingPong,
+}
+
+/// Event emitted by [`Animation::update`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// The clip reached one of its ends and continues playing (see [`Repeat::Loop`] and [`Repeat::PingPong`])
+    Looped,
+    /// The clip reached its end and stops there (see [`Repeat::Once`])
+    Finished,
+}
+
+/// Playback state of a [`Clip`]
+///
+/// The clip itself is not stored, so the same one must be passed to every method until the animation is reset.
+#[derive(Debug, Clone, Default)]
 pub struct Animation {
-    pub current_frame: usize,
-    len: usize,
-    timer: Timer,
+    /// Position in the frames of the clip
+    frame: usize,
+    /// Time spent on the current frame
+    elapsed: Duration,
+    backward: bool,
+    finished: bool,
 }
 
 impl Animation {
-    pub fn new(len: usize, fps: f32) -> Self {
-        Self {
-            current_frame: 0,
-            len,
-            timer: Timer::new(Duration::from_secs_f32(1. / fps)),
+    pub fn update(&mut self, clip: &Clip, delta_time: Duration) -> Option<Event> {
+        if self.finished {
+            return None;
+        }
+        self.elapsed += delta_time;
+        let mut event = None;
+        loop {
+            let duration = clip.frames[self.frame].duration();
+            if self.elapsed < duration {
+                return event;
+            }
+            self.elapsed -= duration;
+            if let Some(e) = self.next_frame(clip) {
+                event = Some(e);
+            }
+            if self.finished {
+                self.elapsed = Duration::ZERO;
+                return event;
+            }
         }
     }
 
-    pub fn update(&mut self, delta_time: Duration) {
-        self.timer.update(delta_time);
-        if self.timer.is_finished() {
-            self.current_frame = (self.current_frame + 1) % self.len;
+    fn next_frame(&mut self, clip: &Clip) -> Option<Event> {
+        let last = clip.frames.len() - 1;
+        match clip.repeat {
+            Repeat::Once if self.frame == last => {
+                self.finished = true;
+                return Some(Event::Finished);
+            }
+            Repeat::Loop if self.frame == last => {
+                self.frame = 0;
+                return Some(Event::Looped);
+            }
+            Repeat::PingPong if last == 0 => return Some(Event::Looped),
+            Repeat::PingPong if self.backward && self.frame == 0 => {
+                self.backward = false;
+                self.frame = 1;
+                return Some(Event::Looped);
+            }
+            Repeat::PingPong if !self.backward && self.frame == last => {
+                self.backward = true;
+                self.frame = last - 1;
+                return Some(Event::Looped);
+            }
+            Repeat::PingPong if self.backward => self.frame -= 1,
+            _ => self.frame += 1,
         }
+        None
+    }
+
+    /// Column of the current frame in the sprite sheet
+    pub fn sheet_index(&self, clip: &Clip) -> usize {
+        clip.frames[self.frame].index
     }
 }
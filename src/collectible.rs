@@ -0,0 +1,35 @@
+use collision::Aabb;
+use crankit_graphics::{Color, Rect};
+
+use crate::{to_screen, IVector, Vector};
+
+/// Diameter of the outer circle, in pixels
+const OUTER_SIZE: i32 = 10;
+
+/// Diameter of the inner circle, in pixels
+const INNER_SIZE: i32 = 4;
+
+/// Optional item counted in the level results
+#[derive(Debug, Clone)]
+pub struct Collectible {
+    /// Top-left of the tile containing the item
+    pub position: Vector,
+}
+
+impl Collectible {
+    pub fn collision_box(&self) -> Aabb {
+        Aabb::from_min_max(self.position, self.position + Vector::new(1., 1.))
+    }
+
+    pub fn draw(&self) {
+        let center = to_screen(self.position + Vector::new(0.5, 0.5));
+        draw_circle(center, OUTER_SIZE, 2);
+        draw_circle(center, INNER_SIZE, 1);
+    }
+}
+
+fn draw_circle(center: IVector, size: i32, line_width: i32) {
+    let top_left = center - IVector::new(size / 2, size / 2);
+    let rect = Rect::new(top_left, [size, size]);
+    crankit_graphics::draw_ellipse(rect, line_width, Color::xor());
+}
@@ -0,0 +1,119 @@
+use alloc::format;
+
+use crankit_graphics::{Color, Rect};
+use crankit_input::{Button, ButtonsState};
+
+use crate::tuning::Tuning;
+
+/// A tuning parameter that can be changed from the menu
+struct Entry {
+    label: &'static str,
+    /// Amount added or removed when pressing right or left
+    step: f32,
+    get: fn(&Tuning) -> f32,
+    set: fn(&mut Tuning, f32),
+}
+
+macro_rules! entry {
+    ($section:ident . $parameter:ident, $step:expr) => {
+        Entry {
+            label: concat!(stringify!($section), ".", stringify!($parameter)),
+            step: $step,
+            get: |tuning| tuning.$section.$parameter,
+            set: |tuning, value| tuning.$section.$parameter = value,
+        }
+    };
+}
+
+const ENTRIES: &[Entry] = &[
+    entry!(player.run_speed, 0.5),
+    entry!(player.ground_acceleration, 5.),
+    entry!(player.ground_deceleration, 5.),
+    entry!(player.air_acceleration, 5.),
+    entry!(player.air_deceleration, 5.),
+    entry!(player.gravity, 1.),
+    entry!(player.jump_velocity, 0.5),
+    entry!(player.jump_cut_factor, 0.05),
+    entry!(player.coyote_time, 0.01),
+    entry!(player.jump_buffer, 0.01),
+    entry!(swim.gravity_factor, 0.05),
+    entry!(swim.buoyancy, 0.5),
+    entry!(swim.speed_factor, 0.05),
+    entry!(swim.stroke_velocity, 0.5),
+    entry!(swim.oxygen, 0.5),
+    entry!(water.raise_speed, 0.05),
+    entry!(water.acceleration, 0.01),
+    entry!(water.tide_amplitude, 0.25),
+    entry!(water.tide_period, 0.5),
+    entry!(water.wave_tension, 5.),
+    entry!(water.wave_damping, 0.5),
+    entry!(water.wave_spread, 5.),
+    entry!(water.splash_factor, 1.),
+    entry!(lift.crank_speed_factor, 0.001),
+    entry!(lift.descent_speed, 0.25),
+    entry!(pump.crank_speed_factor, 0.001),
+    entry!(pump.max_crank_speed, 0.25),
+    entry!(drawbridge.crank_speed_factor, 0.05),
+    entry!(drawbridge.max_crank_speed, 5.),
+    entry!(gate.crank_speed_factor, 0.001),
+    entry!(gate.max_crank_speed, 0.25),
+];
+
+const POSITION: [i32; 2] = [8, 4];
+const WIDTH: i32 = 280;
+const LINE_HEIGHT: i32 = 18;
+/// Number of entries shown at once, the menu is split in pages if there are more
+const PAGE_SIZE: usize = 12;
+
+/// Menu to tweak the [`Tuning`] live, opened and closed with the B button
+///
+/// Up and down select a parameter, left and right change its value.
+#[derive(Debug, Default)]
+pub struct DebugMenu {
+    is_open: bool,
+    selected: usize,
+}
+
+impl DebugMenu {
+    /// Returns true if the menu is open, in which case the game should not be updated
+    pub fn update(&mut self, buttons: ButtonsState, tuning: &mut Tuning) -> bool {
+        if buttons.is_just_pressed(Button::B) {
+            self.is_open = !self.is_open;
+        }
+        if !self.is_open {
+            return false;
+        }
+        if buttons.is_just_pressed(Button::Down) {
+            self.selected = (self.selected + 1) % ENTRIES.len();
+        } else if buttons.is_just_pressed(Button::Up) {
+            self.selected = (self.selected + ENTRIES.len() - 1) % ENTRIES.len();
+        }
+        let entry = &ENTRIES[self.selected];
+        let change = if buttons.is_just_pressed(Button::Right) {
+            entry.step
+        } else if buttons.is_just_pressed(Button::Left) {
+            -entry.step
+        } else {
+            return true;
+        };
+        let value = ((entry.get)(tuning) + change).max(0.);
+        (entry.set)(tuning, value);
+        true
+    }
+
+    pub fn draw(&self, tuning: &Tuning) {
+        if !self.is_open {
+            return;
+        }
+        let [x, y] = POSITION;
+        let first = self.selected - self.selected % PAGE_SIZE;
+        let entries = ENTRIES.iter().enumerate().skip(first).take(PAGE_SIZE);
+        let height = entries.len() as i32 * LINE_HEIGHT;
+        crankit_graphics::fill_rect(Rect::new([x, y], [WIDTH, height]), Color::white());
+        entries.enumerate().for_each(|(line, (i, entry))| {
+            let cursor = if i == self.selected { ">" } else { " " };
+            let text = format!("{cursor} {}: {:.3}", entry.label, (entry.get)(tuning));
+            crankit_graphics::draw_text(&text, [x + 4, y + line as i32 * LINE_HEIGHT]);
+        });
+    }
+}
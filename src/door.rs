@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use collision::Aabb;
+use crankit_graphics::{Color, Rect};
+use math2d::Degrees;
+use serde::Deserialize;
+
+use crate::{
+    key::{self, Inventory, Key, Lock},
+    mechanism::{Drive, Mechanism},
+    to_screen, IVector, Vector,
+};
+
+/// Margin around a door in which the player opens it, in tiles
+const TOUCH_MARGIN: f32 = 0.1;
+
+/// Extent of the gate interaction box beyond the gate, on each side, in tiles
+const GATE_REACH: f32 = 1.5;
+
+/// Tunable parameters of the gates
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Height (in tiles) by which a gate is raised per degree of crank rotation
+    pub crank_speed_factor: f32,
+    /// Maximum speed (in tiles per second) at which a gate is raised
+    pub max_crank_speed: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            crank_speed_factor: 0.01,
+            max_crank_speed: 4.,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+pub enum Kind {
+    /// Vanishes as soon as the player touches it with the required keys
+    #[default]
+    Door,
+    /// Once unlocked, raised by cranking next to it
+    Gate,
+}
+
+/// Description of a door, as placed in the level
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub area: Aabb,
+    pub kind: Kind,
+    /// Keys to carry to open it, none for a door that opens on touch and a gate that can always be cranked
+    pub required_keys: Vec<Key>,
+}
+
+/// Solid obstacle, opened with keys
+#[derive(Debug)]
+pub struct Door {
+    area: Aabb,
+    kind: Kind,
+    lock: Lock,
+    /// Height by which the door is open, in tiles
+    opening: f32,
+}
+
+impl Door {
+    pub fn new(definition: &Definition) -> Self {
+        Self {
+            area: definition.area,
+            kind: definition.kind,
+            lock: Lock::new(definition.required_keys.clone()),
+            opening: 0.,
+        }
+    }
+
+    /// Unlocks the door if the player carries the required keys, and opens it if it is touched
+    pub fn update(&mut self, player_box: Aabb, inventory: &Inventory) {
+        self.lock.unlock(inventory);
+        if self.kind == Kind::Door && self.lock.is_open() && self.is_touched(player_box) {
+            self.opening = self.height();
+        }
+    }
+
+    /// Raise the gate by the crank `rotation`
+    pub fn crank(&mut self, rotation: Degrees, delta_time: Duration, config: &Config) {
+        let drive = Drive {
+            ratio: config.crank_speed_factor,
+            max_speed: Some(config.max_crank_speed),
+            is_reversible: false,
+        };
+        let opening = self.opening + drive.movement(rotation, delta_time);
+        self.opening = opening.min(self.height());
+    }
+
+    /// Solid part of the door, if not fully open
+    pub fn collision_box(&self) -> Option<Aabb> {
+        let [min_y, max_y] = self.area.y.into();
+        let bottom = max_y - self.opening;
+        (bottom > min_y).then(|| {
+            let [min_x, max_x] = self.area.x.into();
+            Aabb::from_min_max([min_x, min_y], [max_x, bottom])
+        })
+    }
+
+    pub fn draw(&self) {
+        let Some(collision_box) = self.collision_box() else {
+            return;
+        };
+        let [min_x, max_x] = collision_box.x.into();
+        let [min_y, max_y] = collision_box.y.into();
+        let top_left = to_screen(Vector::new(min_x, min_y));
+        let bottom_right = to_screen(Vector::new(max_x, max_y));
+        let size = bottom_right - top_left;
+        crankit_graphics::fill_rect(Rect::new(top_left, size), Color::black());
+        let inner_size = size - IVector::new(4, 4);
+        crankit_graphics::fill_rect(
+            Rect::new(top_left + IVector::new(2, 2), inner_size),
+            self.color(),
+        );
+        let center = top_left + IVector::new(size.x / 2, size.y / 2);
+        self.lock.draw(center);
+    }
+
+    /// Color of the first required key, so that the player can tell which key opens the door
+    fn color(&self) -> key::Color {
+        self.lock
+            .required()
+            .first()
+            .map_or(key::Color::Gray, |k| k.color)
+    }
+
+    fn height(&self) -> f32 {
+        let [min_y, max_y] = self.area.y.into();
+        max_y - min_y
+    }
+
+    fn is_touched(&self, player_box: Aabb) -> bool {
+        let margin = Vector::new(TOUCH_MARGIN, TOUCH_MARGIN);
+        let [min_x, max_x] = self.area.x.into();
+        let [min_y, max_y] = self.area.y.into();
+        Aabb::from_min_max(
+            Vector::new(min_x, min_y) - margin,
+            Vector::new(max_x, max_y) + margin,
+        )
+        .collides(player_box)
+    }
+}
+
+impl Mechanism for Door {
+    fn interaction_box(&self) -> Aabb {
+        let [min_x, max_x] = self.area.x.into();
+        let [min_y, max_y] = self.area.y.into();
+        Aabb::from_min_max([min_x - GATE_REACH, min_y], [max_x + GATE_REACH, max_y])
+    }
+
+    fn is_operable(&self) -> bool {
+        self.kind == Kind::Gate && self.lock.is_open() && self.opening < self.height()
+    }
+}
@@ -0,0 +1,146 @@
+use core::time::Duration;
+
+use collision::Aabb;
+use crankit_graphics::Color;
+use math2d::{Angle, Degrees};
+use serde::Deserialize;
+
+use crate::{
+    mechanism::{Drive, Mechanism},
+    to_screen, Vector,
+};
+
+/// Angle of the deck when fully lowered, relative to its raised position
+const LOWERED_ANGLE: f32 = 90.;
+
+/// Thickness of the deck, in tiles
+const THICKNESS: f32 = 0.25;
+
+/// Width of the deck when drawn, in pixels
+const LINE_WIDTH: i32 = 4;
+
+/// Extent of the interaction box around the hinge, in tiles
+const REACH: f32 = 1.5;
+
+/// Side toward which a drawbridge is lowered
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+pub enum Direction {
+    #[default]
+    Right,
+    Left,
+}
+
+impl Direction {
+    fn sign(self) -> f32 {
+        match self {
+            Self::Right => 1.,
+            Self::Left => -1.,
+        }
+    }
+}
+
+/// Tunable parameters of the drawbridges
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Rotation of the deck (in degrees) per degree of crank rotation
+    pub crank_speed_factor: f32,
+    /// Maximum rotation speed of the deck, in degrees per second
+    pub max_crank_speed: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            crank_speed_factor: 0.25,
+            max_crank_speed: 45.,
+        }
+    }
+}
+
+/// Description of a drawbridge, as placed in the level
+#[derive(Debug, Clone)]
+pub struct Definition {
+    /// Top of the deck, on the side of the hinge
+    pub hinge: Vector,
+    /// Length of the deck, in tiles
+    pub length: f32,
+    pub direction: Direction,
+    pub is_lowered: bool,
+}
+
+/// Deck turning around a hinge, lowered by cranking forward and raised by cranking backward
+#[derive(Debug)]
+pub struct Drawbridge {
+    hinge: Vector,
+    length: f32,
+    direction: Direction,
+    /// Rotation from the raised position, in degrees
+    angle: f32,
+}
+
+impl Drawbridge {
+    pub fn new(definition: &Definition) -> Self {
+        Self {
+            hinge: definition.hinge,
+            length: definition.length,
+            direction: definition.direction,
+            angle: if definition.is_lowered {
+                LOWERED_ANGLE
+            } else {
+                0.
+            },
+        }
+    }
+
+    /// Turn the deck by the crank `rotation`
+    pub fn crank(&mut self, rotation: Degrees, delta_time: Duration, config: &Config) {
+        let drive = Drive {
+            ratio: config.crank_speed_factor,
+            max_speed: Some(config.max_crank_speed),
+            is_reversible: true,
+        };
+        let angle = self.angle + drive.movement(rotation, delta_time);
+        self.angle = angle.clamp(0., LOWERED_ANGLE);
+    }
+
+    /// Solid part of the drawbridge, only when fully raised (a wall) or fully lowered (a floor)
+    ///
+    /// The deck is not solid while it turns.
+    pub fn collision_box(&self) -> Option<Aabb> {
+        let [width, top] = if self.angle <= 0. {
+            [THICKNESS, self.hinge.y - self.length]
+        } else if self.angle >= LOWERED_ANGLE {
+            [self.length, self.hinge.y]
+        } else {
+            return None;
+        };
+        let far_x = self.hinge.x + self.direction.sign() * width;
+        Some(Aabb::from_min_max(
+            [self.hinge.x.min(far_x), top],
+            [self.hinge.x.max(far_x), self.hinge.y + THICKNESS],
+        ))
+    }
+
+    pub fn draw(&self) {
+        let angle = Degrees(self.angle);
+        let end = self.hinge
+            + Vector::new(self.direction.sign() * angle.sin(), -angle.cos()) * self.length;
+        crankit_graphics::draw_line(
+            to_screen(self.hinge),
+            to_screen(end),
+            LINE_WIDTH,
+            Color::black(),
+        );
+    }
+}
+
+impl Mechanism for Drawbridge {
+    fn interaction_box(&self) -> Aabb {
+        let reach = Vector::new(REACH, REACH);
+        Aabb::from_min_max(
+            self.hinge - reach,
+            self.hinge + Vector::new(REACH, THICKNESS),
+        )
+    }
+}
@@ -0,0 +1,93 @@
+use collision::Aabb;
+use crankit_graphics::{Color, Rect};
+use math2d::{Angle, Degrees};
+
+use crate::{mechanism::Mechanism, to_screen, IVector, Vector};
+
+/// Radius of the wheel, in pixels
+const RADIUS: i32 = 6;
+
+/// Length of the teeth beyond the wheel, in pixels
+const TOOTH_LENGTH: f32 = 3.;
+
+const TEETH: usize = 6;
+
+/// Extent of the interaction box around the gear, on each side, in tiles
+const REACH: f32 = 1.;
+
+/// Mechanism driven by a gear, identified by its index
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Target {
+    Lift(usize),
+    Pump(usize),
+    Gate(usize),
+    Drawbridge(usize),
+}
+
+/// Description of a gear, as placed in the level
+#[derive(Debug, Clone)]
+pub struct Definition {
+    /// Center of the gear
+    pub position: Vector,
+    /// Rotation transmitted to the target per degree of crank rotation, negative to reverse it
+    pub ratio: f32,
+    pub target: Target,
+}
+
+/// Wheel that transmits the crank rotation to a mechanism out of reach
+#[derive(Debug)]
+pub struct Gear {
+    position: Vector,
+    ratio: f32,
+    target: Target,
+    /// Angle of the wheel, which turns with the crank
+    angle: Degrees,
+}
+
+impl Gear {
+    pub fn new(definition: &Definition) -> Self {
+        Self {
+            position: definition.position,
+            ratio: definition.ratio,
+            target: definition.target,
+            angle: Degrees(0.),
+        }
+    }
+
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    /// Turn the wheel by the crank `rotation`, and returns the rotation transmitted to the target
+    pub fn crank(&mut self, rotation: Degrees) -> Degrees {
+        self.angle = (self.angle + rotation).normalize_positive();
+        rotation * self.ratio
+    }
+
+    pub fn draw(&self) {
+        let center = to_screen(self.position);
+        let top_left = center - IVector::new(RADIUS, RADIUS);
+        let size = [RADIUS * 2, RADIUS * 2];
+        crankit_graphics::draw_ellipse(Rect::new(top_left, size), 2, Color::white());
+        let step = Degrees(360. / TEETH as f32);
+        (0..TEETH).for_each(|i| {
+            let angle = self.angle + step * i as f32;
+            let direction = Vector::new(angle.cos(), angle.sin());
+            let inner = direction * RADIUS as f32;
+            let outer = direction * (RADIUS as f32 + TOOTH_LENGTH);
+            crankit_graphics::draw_line(
+                center + inner.round().as_vector_i32(),
+                center + outer.round().as_vector_i32(),
+                2,
+                Color::white(),
+            );
+        });
+    }
+}
+
+impl Mechanism for Gear {
+    fn interaction_box(&self) -> Aabb {
+        let reach = Vector::new(REACH, REACH);
+        Aabb::from_min_max(self.position - reach, self.position + reach)
+    }
+}
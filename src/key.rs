@@ -0,0 +1,160 @@
+use alloc::{string::String, vec::Vec};
+
+use collision::Aabb;
+use crankit_graphics::{color::Pattern, image::Image, Rect};
+use serde::Deserialize;
+
+use crate::{to_screen, IVector, Vector};
+
+/// Size of the color tag drawn on keys and locks, in pixels
+const TAG_SIZE: i32 = 6;
+
+/// Position of the tag relative to the top-left of the key image
+const TAG_OFFSET: IVector = IVector::new(10, 10);
+
+/// Identifier of a key, unique in the level
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Id(pub String);
+
+/// Color of a key, telling the player which locks it opens
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+pub enum Color {
+    #[default]
+    White,
+    Black,
+    Gray,
+}
+
+impl Color {
+    /// Draws a small square of the color, with a black border, centered on `center`
+    pub fn draw_tag(self, center: IVector) {
+        let top_left = center - IVector::new(TAG_SIZE / 2, TAG_SIZE / 2);
+        crankit_graphics::fill_rect(
+            Rect::new(top_left, [TAG_SIZE, TAG_SIZE]),
+            crankit_graphics::Color::black(),
+        );
+        crankit_graphics::fill_rect(
+            Rect::new(top_left + IVector::new(1, 1), [TAG_SIZE - 2, TAG_SIZE - 2]),
+            self,
+        );
+    }
+}
+
+impl From<Color> for crankit_graphics::Color {
+    fn from(value: Color) -> Self {
+        match value {
+            Color::White => Self::white(),
+            Color::Black => Self::black(),
+            Color::Gray => {
+                Pattern::from_black([0xaa, 0x55, 0xaa, 0x55, 0xaa, 0x55, 0xaa, 0x55]).into()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Key {
+    pub id: Id,
+    pub color: Color,
+}
+
+impl Key {
+    /// Draws the key with its upper-left corner at `position` (in pixels)
+    pub fn draw(&self, image: &Image, position: IVector) {
+        image.draw(position);
+        self.color.draw_tag(position + TAG_OFFSET);
+    }
+}
+
+/// Key lying in the level, waiting to be collected
+#[derive(Debug, Clone)]
+pub struct Pickup {
+    pub key: Key,
+    /// Top-left of the key
+    pub position: Vector,
+}
+
+impl Pickup {
+    pub fn collision_box(&self) -> Aabb {
+        Aabb::from_min_max(self.position, self.position + Vector::new(1., 1.))
+    }
+
+    pub fn draw(&self, image: &Image) {
+        self.key.draw(image, to_screen(self.position));
+    }
+}
+
+/// Something that can only be used once the player carries all the required keys
+///
+/// Keys are not consumed, so that one key can open several locks.
+#[derive(Debug, Clone)]
+pub struct Lock {
+    required: Vec<Key>,
+    is_open: bool,
+}
+
+impl Lock {
+    pub fn new(required: Vec<Key>) -> Self {
+        let is_open = required.is_empty();
+        Self { required, is_open }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn required(&self) -> &[Key] {
+        &self.required
+    }
+
+    /// Opens the lock if the `inventory` contains all the required keys
+    ///
+    /// Once open, the lock stays open.
+    pub fn unlock(&mut self, inventory: &Inventory) {
+        self.is_open = self.is_open || self.required.iter().all(|k| inventory.contains(&k.id));
+    }
+
+    /// Draws the tags of the required keys in a row centered on `center`, unless the lock is open
+    pub fn draw(&self, center: IVector) {
+        if self.is_open {
+            return;
+        }
+        let width = self.required.len() as i32 * (TAG_SIZE + 1);
+        let left = center.x - width / 2 + TAG_SIZE / 2;
+        self.required.iter().enumerate().for_each(|(i, key)| {
+            let x = left + i as i32 * (TAG_SIZE + 1);
+            key.color.draw_tag(IVector::new(x, center.y));
+        });
+    }
+}
+
+/// Keys carried by the player
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    keys: Vec<Key>,
+}
+
+/// Position of the first key of the inventory on screen
+const INVENTORY_TOP_LEFT: IVector = IVector::new(4, 4);
+
+/// Space between two keys of the inventory, in pixels
+const INVENTORY_SPACING: i32 = 2;
+
+impl Inventory {
+    pub fn add(&mut self, key: Key) {
+        self.keys.push(key);
+    }
+
+    pub fn contains(&self, id: &Id) -> bool {
+        self.keys.iter().any(|k| k.id == *id)
+    }
+
+    /// Draws the carried keys in a row at the top-left of the screen
+    pub fn draw(&self, image: &Image) {
+        let [width, _] = image.size();
+        self.keys.iter().enumerate().for_each(|(i, key)| {
+            let x = i as i32 * (width + INVENTORY_SPACING);
+            key.draw(image, INVENTORY_TOP_LEFT + IVector::new(x, 0));
+        });
+    }
+}
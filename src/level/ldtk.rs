@@ -1,20 +1,25 @@
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 use anyhow::anyhow;
 use serde::Deserialize;
+use serde_json::{Map, Value};
 
+use collision::Aabb;
 use grid::Grid;
 use math2d::Vector;
 
-use crate::TILE_SIZE;
+use crate::{collectible::Collectible, door, drawbridge, gear, key, lift, pump, TILE_SIZE};
 
-use super::Cell;
+use super::{Cell, WaterTrigger};
 
 const RAW_DATA: &[&str] = &[
     include_str!("../../assets/levels/simplified/level_0/data.json"),
     include_str!("../../assets/levels/simplified/level_1/data.json"),
 ];
 
+/// Number of levels in the game
+pub const LEVEL_COUNT: usize = RAW_DATA.len();
+
 const RAW_INT_GRIDS: &[&str] = &[
     include_str!("../../assets/levels/simplified/level_0/foreground.csv"),
     include_str!("../../assets/levels/simplified/level_1/foreground.csv"),
@@ -24,6 +29,8 @@ const RAW_INT_GRIDS: &[&str] = &[
 pub struct Data {
     pub width: usize,
     pub height: usize,
+    #[serde(rename = "customFields", default)]
+    pub custom_fields: Map<String, Value>,
     pub entities: Entities,
 }
 
@@ -43,34 +50,426 @@ pub struct Entities {
     pub player: [Vector; 1],
     #[serde(rename = "lift")]
     pub lifts: Vec<Lift>,
+    #[serde(rename = "water_trigger", default)]
+    pub water_triggers: Vec<WaterTriggerEntity>,
+    #[serde(rename = "pump", default)]
+    pub pumps: Vec<Pump>,
+    #[serde(rename = "key", default)]
+    pub keys: Vec<Key>,
+    #[serde(rename = "door", default)]
+    pub doors: Vec<Door>,
+    #[serde(rename = "collectible", default)]
+    pub collectibles: Vec<CollectibleEntity>,
+    #[serde(rename = "drawbridge", default)]
+    pub drawbridges: Vec<Drawbridge>,
+    #[serde(rename = "gear", default)]
+    pub gears: Vec<Gear>,
+}
+
+impl Entities {
+    /// Ids of the entities a gear can drive, with the mechanism each one becomes in the level
+    pub fn gear_targets(&self) -> Vec<(String, gear::Target)> {
+        let lifts = self.lifts.iter().map(|l| &l.iid);
+        let pumps = self.pumps.iter().map(|p| &p.iid);
+        let doors = self.doors.iter().map(|d| &d.iid);
+        let drawbridges = self.drawbridges.iter().map(|d| &d.iid);
+        let lifts = lifts
+            .enumerate()
+            .map(|(i, iid)| (iid, gear::Target::Lift(i)));
+        let pumps = pumps
+            .enumerate()
+            .map(|(i, iid)| (iid, gear::Target::Pump(i)));
+        let gates = doors
+            .enumerate()
+            .map(|(i, iid)| (iid, gear::Target::Gate(i)));
+        let drawbridges = drawbridges
+            .enumerate()
+            .map(|(i, iid)| (iid, gear::Target::Drawbridge(i)));
+        lifts
+            .chain(pumps)
+            .chain(gates)
+            .chain(drawbridges)
+            .map(|(iid, target)| (iid.clone(), target))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Lift {
+    pub iid: String,
     #[serde(flatten)]
     pub position: Vector,
     pub custom_fields: LiftCustomFields,
 }
 
-impl From<Lift> for (Vector, Option<Vector>, f32) {
-    fn from(value: Lift) -> Self {
-        let base = value.position / TILE_SIZE;
-        let key = value.custom_fields.key.map(Into::into);
-        let height = value
-            .custom_fields
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LiftCustomFields {
+    pub arrival: Option<Point>,
+    /// Position of a key only used by this lift (prefer `RequiredKeys` with key entities)
+    pub key: Option<Point>,
+    /// Positions of additional keys only used by this lift
+    #[serde(default)]
+    pub keys: Vec<Point>,
+    /// References to the key entities required to unlock the lift
+    #[serde(default)]
+    pub required_keys: Vec<EntityRef>,
+    pub behavior: Option<lift::Behavior>,
+    pub max_crank_speed: Option<f32>,
+    pub counterweight: Option<EntityRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityRef {
+    pub entity_iid: String,
+}
+
+/// Convert the lift entities, resolving the counterweight references to indices in the result
+///
+/// The keys given as positions in the lift fields are added to `keys`, so that they can be collected like key entities.
+/// A counterweight only needs to be referenced by one of the two lifts, the link is set on both.
+///
+/// # Errors
+///
+/// Returns an error if the custom fields of a lift are invalid, or if a counterweight reference is
+/// dangling, points to the lift itself, or conflicts with the counterweight of the other lift.
+pub fn lifts(
+    entities: Vec<Lift>,
+    keys: &mut Vec<key::Pickup>,
+) -> anyhow::Result<Vec<lift::Definition>> {
+    let iids: Vec<String> = entities.iter().map(|l| l.iid.clone()).collect();
+    let mut lifts = entities
+        .into_iter()
+        .map(|l| l.into_definition(&iids, keys))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    for i in 0..lifts.len() {
+        let Some(c) = lifts[i].counterweight else {
+            continue;
+        };
+        if c == i {
+            return Err(anyhow!("lift {} is its own counterweight", iids[i]));
+        }
+        match lifts[c].counterweight {
+            None => lifts[c].counterweight = Some(i),
+            Some(other) if other == i => (),
+            Some(other) => {
+                return Err(anyhow!(
+                    "lift {} has the counterweight {}, but {} has the counterweight {}",
+                    iids[i],
+                    iids[c],
+                    iids[c],
+                    iids[other]
+                ))
+            }
+        }
+    }
+    Ok(lifts)
+}
+
+impl Lift {
+    /// `iids` are the ids of all the lifts of the level, used to find the index of the counterweight
+    fn into_definition(
+        self,
+        iids: &[String],
+        keys: &mut Vec<key::Pickup>,
+    ) -> anyhow::Result<lift::Definition> {
+        let fields = self.custom_fields;
+        let max_crank_speed =
+            non_negative(fields.max_crank_speed, "lift", &self.iid, "MaxCrankSpeed")?;
+        let start = self.position / TILE_SIZE;
+        let end = fields
             .arrival
-            .map(|p| base.y - p.cy - 1.)
-            .unwrap_or_default();
-        (base, key, height)
+            .map_or(start, |p| Vector::new(p.cx + 0.5, p.cy + 1.));
+        let mut required_keys = required_keys(&fields.required_keys, keys)
+            .map_err(|err| anyhow!("invalid lift {}: {err}", self.iid))?;
+        for (i, point) in fields.key.into_iter().chain(fields.keys).enumerate() {
+            let key = key::Key {
+                id: key::Id(format!("{}-key-{i}", self.iid)),
+                color: key::Color::default(),
+            };
+            required_keys.push(key.clone());
+            keys.push(key::Pickup {
+                key,
+                position: point.into(),
+            });
+        }
+        let counterweight = fields
+            .counterweight
+            .map(|r| {
+                iids.iter()
+                    .position(|iid| *iid == r.entity_iid)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "lift {} has the counterweight {}, which is not a lift",
+                            self.iid,
+                            r.entity_iid
+                        )
+                    })
+            })
+            .transpose()?;
+        Ok(lift::Definition {
+            start,
+            end,
+            required_keys,
+            behavior: fields.behavior.unwrap_or_default(),
+            max_crank_speed,
+            counterweight,
+        })
+    }
+}
+
+/// Returns the optional `value` of the `field` of an entity, or an error if it is negative or not finite
+fn non_negative(
+    value: Option<f32>,
+    entity: &str,
+    iid: &str,
+    field: &str,
+) -> anyhow::Result<Option<f32>> {
+    match value {
+        Some(value) if !(value.is_finite() && value >= 0.) => Err(anyhow!(
+            "invalid {entity} {iid}: {field} must be a non-negative number, got {value}"
+        )),
+        _ => Ok(value),
+    }
+}
+
+/// Keys referenced by `refs`
+///
+/// # Errors
+///
+/// Returns an error naming the first reference that is not a key of `keys`.
+fn required_keys(refs: &[EntityRef], keys: &[key::Pickup]) -> anyhow::Result<Vec<key::Key>> {
+    refs.iter()
+        .map(|r| {
+            keys.iter()
+                .find(|p| p.key.id.0 == r.entity_iid)
+                .map(|p| p.key.clone())
+                .ok_or_else(|| anyhow!("required key {} is not a key", r.entity_iid))
+        })
+        .collect()
+}
+
+/// Key entity, expected to have its pivot at the top-left
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Key {
+    pub iid: String,
+    #[serde(flatten)]
+    pub position: Vector,
+    pub custom_fields: KeyCustomFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct KeyCustomFields {
+    pub color: Option<key::Color>,
+}
+
+impl From<Key> for key::Pickup {
+    fn from(value: Key) -> Self {
+        Self {
+            key: key::Key {
+                id: key::Id(value.iid),
+                color: value.custom_fields.color.unwrap_or_default(),
+            },
+            position: value.position / TILE_SIZE,
+        }
     }
 }
 
+/// Door entity, expected to have its pivot at the top-left
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Door {
+    pub iid: String,
+    #[serde(flatten)]
+    pub position: Vector,
+    pub width: f32,
+    pub height: f32,
+    pub custom_fields: DoorCustomFields,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct LiftCustomFields {
-    pub arrival: Option<Point>,
-    pub key: Option<Point>,
+pub struct DoorCustomFields {
+    pub kind: Option<door::Kind>,
+    #[serde(default)]
+    pub required_keys: Vec<EntityRef>,
+}
+
+impl Door {
+    /// `keys` are all the keys of the level, used to resolve the references to the required keys
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required key is not one of `keys`.
+    pub fn into_definition(self, keys: &[key::Pickup]) -> anyhow::Result<door::Definition> {
+        let min = self.position / TILE_SIZE;
+        let max = (self.position + Vector::new(self.width, self.height)) / TILE_SIZE;
+        Ok(door::Definition {
+            area: Aabb::from_min_max(min, max),
+            kind: self.custom_fields.kind.unwrap_or_default(),
+            required_keys: required_keys(&self.custom_fields.required_keys, keys)
+                .map_err(|err| anyhow!("invalid door {}: {err}", self.iid))?,
+        })
+    }
+}
+
+/// Collectible entity, expected to have its pivot at the top-left
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectibleEntity {
+    #[serde(flatten)]
+    pub position: Vector,
+}
+
+impl From<CollectibleEntity> for Collectible {
+    fn from(value: CollectibleEntity) -> Self {
+        Self {
+            position: value.position / TILE_SIZE,
+        }
+    }
+}
+
+/// Pump entity, expected to have its pivot at the bottom-center
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pump {
+    pub iid: String,
+    #[serde(flatten)]
+    pub position: Vector,
+    pub custom_fields: PumpCustomFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PumpCustomFields {
+    /// Height (in tiles) by which the water is lowered per degree of crank rotation
+    pub ratio: Option<f32>,
+    /// In tiles per second
+    pub max_speed: Option<f32>,
+}
+
+impl Pump {
+    /// # Errors
+    ///
+    /// Returns an error if the maximum speed is negative or not finite.
+    pub fn into_definition(self) -> anyhow::Result<pump::Definition> {
+        let fields = self.custom_fields;
+        Ok(pump::Definition {
+            position: self.position / TILE_SIZE,
+            ratio: fields.ratio,
+            max_speed: non_negative(fields.max_speed, "pump", &self.iid, "MaxSpeed")?,
+        })
+    }
+}
+
+/// Drawbridge entity, expected to have its pivot at the hinge (top of the deck)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Drawbridge {
+    pub iid: String,
+    #[serde(flatten)]
+    pub position: Vector,
+    pub custom_fields: DrawbridgeCustomFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DrawbridgeCustomFields {
+    /// In tiles
+    pub length: f32,
+    pub direction: Option<drawbridge::Direction>,
+    #[serde(default)]
+    pub is_lowered: bool,
+}
+
+impl From<Drawbridge> for drawbridge::Definition {
+    fn from(value: Drawbridge) -> Self {
+        Self {
+            hinge: value.position / TILE_SIZE,
+            length: value.custom_fields.length,
+            direction: value.custom_fields.direction.unwrap_or_default(),
+            is_lowered: value.custom_fields.is_lowered,
+        }
+    }
+}
+
+/// Gear entity, expected to have its pivot at the center
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gear {
+    pub iid: String,
+    #[serde(flatten)]
+    pub position: Vector,
+    pub custom_fields: GearCustomFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GearCustomFields {
+    /// Rotation transmitted per degree of crank rotation, 1 if not set
+    pub ratio: Option<f32>,
+    /// Lift, pump, door or drawbridge driven by the gear
+    pub target: EntityRef,
+}
+
+impl Gear {
+    /// `targets` are the entities a gear can drive (see [`Entities::gear_targets`])
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the target if it is not one of them.
+    pub fn into_definition(
+        self,
+        targets: &[(String, gear::Target)],
+    ) -> anyhow::Result<gear::Definition> {
+        let fields = self.custom_fields;
+        let (_, target) = targets
+            .iter()
+            .find(|(iid, _)| *iid == fields.target.entity_iid)
+            .ok_or_else(|| {
+                anyhow!(
+                    "gear {} has the target {}, which cannot be driven by a gear",
+                    self.iid,
+                    fields.target.entity_iid
+                )
+            })?;
+        Ok(gear::Definition {
+            position: self.position / TILE_SIZE,
+            ratio: fields.ratio.unwrap_or(1.),
+            target: *target,
+        })
+    }
+}
+
+/// Water trigger entity, expected to have its pivot at the top-left
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaterTriggerEntity {
+    #[serde(flatten)]
+    pub position: Vector,
+    pub width: f32,
+    pub height: f32,
+    pub custom_fields: WaterTriggerCustomFields,
+}
+
+impl From<WaterTriggerEntity> for WaterTrigger {
+    fn from(value: WaterTriggerEntity) -> Self {
+        let min = value.position / TILE_SIZE;
+        let max = (value.position + Vector::new(value.width, value.height)) / TILE_SIZE;
+        Self {
+            area: Aabb::from_min_max(min, max),
+            shift: value.custom_fields.shift,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WaterTriggerCustomFields {
+    pub shift: f32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -96,6 +495,7 @@ pub fn load_grid(level_num: usize, width: usize, height: usize) -> anyhow::Resul
         .map(|v| match v {
             "1" => Cell::Terrain,
             "2" => Cell::Hazard,
+            "3" => Cell::Crumbling,
             _ => Cell::Empty,
         });
     Ok(Grid::from_iter(width, height, cells))
@@ -8,57 +8,363 @@ use playdate_sys::println;
 use collision::Aabb;
 use crankit_graphics::{image::Image, LoadError};
 use crankit_input::ButtonsState;
-use grid::Grid;
+use grid::{Grid, TrackedGrid};
+use timer::Stopwatch;
 
-use crate::{lift::Lift, player::Player, water::Water, Images, Vector, TILE_SIZE};
+use crate::{
+    collectible::Collectible,
+    door::{self, Door},
+    drawbridge::{self, Drawbridge},
+    gear::{self, Gear},
+    key,
+    lift::{self, Lift},
+    mechanism::{self, Crank, Mechanism},
+    platform::{self, Contact},
+    player::Player,
+    pump::{self, Pump},
+    results::Stats,
+    shake::Shake,
+    tuning::Tuning,
+    water::Water,
+    IVector, Images, Vector, TILE_SIZE,
+};
+
+use self::terrain::{Crumbles, TerrainImage};
 
 const PENETRATION_RESOLUTION_MAX_ITER: u32 = 10;
 
 pub struct Level {
     definition: Definition,
+    terrain: TrackedGrid<Cell>,
+    terrain_image: TerrainImage,
+    crumbles: Crumbles,
     player: Player,
     water: Water,
     lifts: Vec<Lift>,
+    pumps: Vec<Pump>,
+    doors: Vec<Door>,
+    drawbridges: Vec<Drawbridge>,
+    gears: Vec<Gear>,
+    /// Keys not collected yet
+    keys: Vec<key::Pickup>,
+    /// Collectibles not found yet
+    collectibles: Vec<Collectible>,
+    /// Time since the level started, kept across restarts
+    time: Stopwatch,
+    deaths: u32,
+    /// Water triggers not activated yet
+    water_triggers: Vec<WaterTrigger>,
+    shake: Option<Shake>,
+    /// Starts as the level definition tuning, but is kept across restarts to not lose live changes
+    tuning: Tuning,
+}
+
+/// Something that happened in the level, to be handled by the game
+#[derive(Debug, Copy, Clone)]
+pub enum Event {
+    /// The player reached the top of the level
+    Completed(Stats),
 }
 
 impl Level {
-    pub fn update(&mut self, delta_time: Duration, buttons: ButtonsState, crank_change: f32) {
-        self.player.handle_input(buttons);
-        self.player.update(delta_time);
-        let player_collision_box = self.player.collision_box();
+    pub fn update(
+        &mut self,
+        images: &Images,
+        delta_time: Duration,
+        buttons: ButtonsState,
+        crank: Crank,
+    ) -> Option<Event> {
+        self.time.update(delta_time);
+        let previous_player_position = self.player.position();
+        self.player.handle_input(buttons, &self.tuning.player);
+        self.player.update(
+            delta_time,
+            &images.player,
+            &self.tuning.player,
+            &self.tuning.swim,
+            self.water.vertical_position(),
+        );
+        if self.player.is_dying() {
+            if let Some(shake) = &mut self.shake {
+                shake.update(delta_time);
+            }
+            if self.player.is_dead() {
+                self.deaths += 1;
+                self.restart();
+            }
+            return None;
+        }
         self.resolve_collisions();
-        self.lifts.iter_mut().for_each(|lift| {
-            lift.set_active(lift.interaction_box().collides(player_collision_box));
-            lift.update(delta_time, crank_change, &mut self.player);
-        });
-        self.water.update(delta_time);
-        if self.collides_against_hazard() {
-            *self = self.definition.clone().into();
+        let player_collision_box = self.player.collision_box();
+        self.crumbles.touch(&self.terrain, player_collision_box);
+        self.crumbles.update(delta_time, &mut self.terrain);
+        self.terrain_image.redraw(&mut self.terrain, &images.tiles);
+        self.collect_keys();
+        self.collect_collectibles();
+        let inventory = self.player.inventory();
+        self.doors
+            .iter_mut()
+            .for_each(|d| d.update(player_collision_box, inventory));
+        let operated = self.select_mechanism(player_collision_box);
+        let rotation = crank.rotation(buttons, delta_time);
+        // A gear transmits the rotation to the mechanism it drives, if that one can be operated
+        let (driven, rotation) = match operated {
+            Some(Operated::Gear(i)) => {
+                let target = Operated::from(self.gears[i].target());
+                let rotation = self.gears[i].crank(rotation);
+                let is_operable = self.mechanism(target).is_operable();
+                (is_operable.then_some(target), rotation)
+            }
+            _ => (operated, rotation),
+        };
+        let mut is_crushed = false;
+        for i in 0..self.lifts.len() {
+            let lift = &self.lifts[i];
+            let previous_position = lift.position();
+            let counterweight = lift.counterweight();
+            let max_distance =
+                counterweight.map_or(f32::INFINITY, |c| self.lifts[c].distance_to_start());
+            let cranked = (driven == Some(Operated::Lift(i)))
+                .then(|| lift.drive(&self.tuning.lift).movement(rotation, delta_time));
+            let distance = self.lifts[i].update(
+                delta_time,
+                cranked,
+                self.player.inventory(),
+                &self.tuning.lift,
+                max_distance,
+            );
+            is_crushed |= self.move_riders(i, previous_position);
+            if let Some(c) = counterweight {
+                let previous_position = self.lifts[c].position();
+                self.lifts[c].move_along(-distance);
+                is_crushed |= self.move_riders(c, previous_position);
+            }
+            let position = self.lifts[i].position();
+            self.water.splash_on_entry(
+                position.x,
+                previous_position.y,
+                position.y,
+                delta_time,
+                &self.tuning.water,
+            );
+        }
+        let player_position = self.player.position();
+        self.water.splash_on_entry(
+            player_position.x,
+            previous_player_position.y,
+            player_position.y,
+            delta_time,
+            &self.tuning.water,
+        );
+        match driven {
+            Some(Operated::Pump(i)) => {
+                let lowered = self.pumps[i].crank(rotation, delta_time, &self.tuning.pump);
+                self.water.shift(-lowered);
+            }
+            Some(Operated::Gate(i)) => {
+                self.doors[i].crank(rotation, delta_time, &self.tuning.gate);
+            }
+            Some(Operated::Drawbridge(i)) => {
+                self.drawbridges[i].crank(rotation, delta_time, &self.tuning.drawbridge);
+            }
+            Some(Operated::Lift(_) | Operated::Gear(_)) | None => (),
         }
+        let is_cranking = matches!(driven, Some(Operated::Lift(_))) && rotation.0 != 0.;
+        self.water
+            .update(delta_time, &self.tuning.water, is_cranking);
+        self.update_water_triggers();
+        if is_crushed || self.player.has_drowned() || self.collides_against_hazard() {
+            self.player.die();
+            self.shake = Some(Shake::new());
+            None
+        } else if self.player.position().y <= 0.01 {
+            Some(Event::Completed(self.stats()))
+        } else {
+            None
+        }
+    }
+
+    /// Put the level back in its initial state, reverting only the terrain cells that changed
+    fn restart(&mut self) {
+        self.terrain.reset();
+        self.terrain.clear_dirty();
+        self.terrain_image = terrain_image(&self.definition);
+        self.crumbles = Crumbles::default();
+        self.player = Player::new(self.definition.player_start);
+        self.lifts = lifts(&self.definition);
+        self.pumps = pumps(&self.definition);
+        self.doors = doors(&self.definition);
+        self.drawbridges = drawbridges(&self.definition);
+        self.gears = gears(&self.definition);
+        self.keys = self.definition.keys.clone();
+        self.collectibles = self.definition.collectibles.clone();
+        self.water_triggers = self.definition.water_triggers.clone();
+        self.water = Water::new(&self.tuning.water);
+        self.shake = None;
     }
 
-    pub fn is_over(&self) -> bool {
-        self.player.position().y <= 0.01
+    #[cfg(feature = "debug-menu")]
+    pub fn tuning(&self) -> &Tuning {
+        &self.tuning
     }
 
-    pub fn next(self) -> Option<Self> {
-        Some(Definition::load(self.definition.num + 1).ok()?.into())
+    #[cfg(feature = "debug-menu")]
+    pub fn tuning_mut(&mut self) -> &mut Tuning {
+        &mut self.tuning
+    }
+
+    pub fn num(&self) -> u8 {
+        self.definition.num
+    }
+
+    fn stats(&self) -> Stats {
+        let total_collectibles = self.definition.collectibles.len();
+        Stats {
+            time: self.time.elapsed(),
+            deaths: self.deaths,
+            collectibles: total_collectibles - self.collectibles.len(),
+            total_collectibles,
+        }
+    }
+
+    /// Load the level following this one, or returns `None` if this was the last level
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the next level exists but cannot be loaded.
+    pub fn next(self) -> anyhow::Result<Option<Self>> {
+        let num = self.definition.num + 1;
+        if usize::from(num) >= ldtk::LEVEL_COUNT {
+            return Ok(None);
+        }
+        Ok(Some(Definition::load(num)?.into()))
     }
 
     pub fn draw(&self, images: &Images) {
+        let offset = self.shake.as_ref().map_or(IVector::ZERO, Shake::offset);
+        crankit_graphics::set_draw_offset(offset);
         self.definition
             .background
             .iter()
             .for_each(|i| i.draw([0, 0]));
         self.player.draw(&images.player);
-        self.lifts
+        self.lifts.iter().for_each(|l| l.draw(&images.lift));
+        self.pumps.iter().for_each(Pump::draw);
+        self.doors.iter().for_each(Door::draw);
+        self.drawbridges.iter().for_each(Drawbridge::draw);
+        self.gears.iter().for_each(Gear::draw);
+        self.keys.iter().for_each(|k| k.draw(&images.key));
+        self.collectibles.iter().for_each(Collectible::draw);
+        self.terrain_image.draw();
+        self.definition.foreground[1].draw([0, 0]);
+        self.water.draw(&images.water);
+        self.player.draw_hud();
+        crankit_graphics::set_draw_offset([0, 0]);
+        self.player.inventory().draw(&images.key);
+    }
+
+    /// Move the keys touched by the player into its inventory
+    fn collect_keys(&mut self) {
+        let player_collision_box = self.player.collision_box();
+        let player = &mut self.player;
+        self.keys.retain(|pickup| {
+            let is_collected = pickup.collision_box().collides(player_collision_box);
+            if is_collected {
+                player.collect(pickup.key.clone());
+            }
+            !is_collected
+        });
+    }
+
+    /// Remove the collectibles touched by the player, which then count as found
+    fn collect_collectibles(&mut self) {
+        let player_collision_box = self.player.collision_box();
+        self.collectibles
+            .retain(|c| !c.collision_box().collides(player_collision_box));
+    }
+
+    /// Shift the water for each trigger touched by the player, which is then removed
+    fn update_water_triggers(&mut self) {
+        let player_collision_box = self.player.collision_box();
+        let water = &mut self.water;
+        self.water_triggers.retain(|trigger| {
+            let is_triggered = trigger.area.collides(player_collision_box);
+            if is_triggered {
+                water.shift(trigger.shift);
+            }
+            !is_triggered
+        });
+    }
+
+    /// Returns the mechanism operated by the player, if any
+    fn select_mechanism(&self, player_box: Aabb) -> Option<Operated> {
+        let lifts = self
+            .lifts
             .iter()
-            .for_each(|l| l.draw(&images.lift, &images.key));
-        self.definition
-            .foreground
+            .enumerate()
+            .map(|(i, l)| (Operated::Lift(i), l as &dyn Mechanism));
+        let pumps = self
+            .pumps
             .iter()
-            .for_each(|i| i.draw([0, 0]));
-        self.water.draw(&images.water);
+            .enumerate()
+            .map(|(i, p)| (Operated::Pump(i), p as &dyn Mechanism));
+        let gates = self
+            .doors
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (Operated::Gate(i), d as &dyn Mechanism));
+        let drawbridges = self
+            .drawbridges
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (Operated::Drawbridge(i), d as &dyn Mechanism));
+        let gears = self
+            .gears
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (Operated::Gear(i), g as &dyn Mechanism));
+        let mechanisms = lifts
+            .chain(pumps)
+            .chain(gates)
+            .chain(drawbridges)
+            .chain(gears);
+        mechanism::select(player_box, mechanisms)
+    }
+
+    fn mechanism(&self, operated: Operated) -> &dyn Mechanism {
+        match operated {
+            Operated::Lift(i) => &self.lifts[i],
+            Operated::Pump(i) => &self.pumps[i],
+            Operated::Gate(i) => &self.doors[i],
+            Operated::Drawbridge(i) => &self.drawbridges[i],
+            Operated::Gear(i) => &self.gears[i],
+        }
+    }
+
+    /// Carry, push or crush the player according to the movement of the lift at `index` since `previous_position`
+    ///
+    /// Returns true if the player is crushed.
+    fn move_riders(&mut self, index: usize, previous_position: Vector) -> bool {
+        let lift = &self.lifts[index];
+        let displacement = lift.position() - previous_position;
+        if displacement == Vector::ZERO {
+            return false;
+        }
+        let previous_box = lift.collision_box().translated(-displacement);
+        let terrain = &self.terrain;
+        let lifts = &self.lifts;
+        let doors = &self.doors;
+        let drawbridges = &self.drawbridges;
+        let contact = platform::move_body(previous_box, displacement, &mut self.player, |area| {
+            area.collides_any(terrain_boxes(terrain, area))
+                || area.collides_any(doors.iter().filter_map(Door::collision_box))
+                || area.collides_any(drawbridges.iter().filter_map(Drawbridge::collision_box))
+                || lifts
+                    .iter()
+                    .enumerate()
+                    .any(|(i, l)| i != index && l.collision_box().collides(area))
+        });
+        contact == Contact::Crushed
     }
 
     fn resolve_collisions(&mut self) {
@@ -83,26 +389,22 @@ impl Level {
 
     fn collides_against_terrain(&self) -> Option<Vector> {
         let player_collision_box = self.player.collision_box();
-        let terrain = coords(player_collision_box)
-            .filter(|c| matches!(self.definition.grid.get(*c), Some(Cell::Terrain)))
-            .map(|[x, y]| {
-                Aabb::from_min_max([x as f32, y as f32], [(x + 1) as f32, (y + 1) as f32])
-            });
+        let terrain = terrain_boxes(&self.terrain, player_collision_box);
         let lifts = self.lifts.iter().map(|l| l.collision_box());
+        let doors = self.doors.iter().filter_map(Door::collision_box);
+        let drawbridges = self
+            .drawbridges
+            .iter()
+            .filter_map(Drawbridge::collision_box);
         player_collision_box
-            .max_penetration(terrain.chain(lifts))
+            .max_penetration(terrain.chain(lifts).chain(doors).chain(drawbridges))
             .map(Into::into)
     }
 
     fn collides_against_hazard(&self) -> bool {
-        let water_vertical_pos = self.water.vertical_position();
-        let player_vertical_pos = self.player.position().y;
-        if player_vertical_pos > water_vertical_pos {
-            return true;
-        }
         let player_collision_box = self.player.collision_box();
         let hazards = coords(player_collision_box)
-            .filter(|c| matches!(self.definition.grid.get(*c), Some(Cell::Hazard)))
+            .filter(|c| matches!(self.terrain.get(*c), Some(Cell::Hazard)))
             .map(|[x, y]| {
                 Aabb::from_min_max(
                     [x as f32 + 0.1, y as f32 + 0.1],
@@ -116,21 +418,93 @@ impl Level {
 impl From<Definition> for Level {
     fn from(definition: Definition) -> Self {
         let player = Player::new(definition.player_start);
-        let lifts = definition
-            .lifts
-            .iter()
-            .copied()
-            .map(|(base, key, height)| Lift::new(base, key, height))
-            .collect();
+        let lifts = lifts(&definition);
+        let pumps = pumps(&definition);
+        let doors = doors(&definition);
+        let drawbridges = drawbridges(&definition);
+        let gears = gears(&definition);
+        let keys = definition.keys.clone();
+        let collectibles = definition.collectibles.clone();
+        let terrain = definition.grid.clone().into();
+        let terrain_image = terrain_image(&definition);
+        let tuning = definition.tuning.clone();
+        let water = Water::new(&tuning.water);
+        let water_triggers = definition.water_triggers.clone();
         Self {
             definition,
+            terrain,
+            terrain_image,
+            crumbles: Crumbles::default(),
             player,
             lifts,
-            water: Water::new(),
+            pumps,
+            doors,
+            drawbridges,
+            gears,
+            keys,
+            collectibles,
+            time: Stopwatch::new(),
+            deaths: 0,
+            water,
+            water_triggers,
+            shake: None,
+            tuning,
         }
     }
 }
 
+fn lifts(definition: &Definition) -> Vec<Lift> {
+    definition.lifts.iter().map(Lift::new).collect()
+}
+
+fn terrain_image(definition: &Definition) -> TerrainImage {
+    TerrainImage::new(definition.foreground[0].clone(), &definition.grid)
+}
+
+fn pumps(definition: &Definition) -> Vec<Pump> {
+    definition.pumps.iter().map(Pump::new).collect()
+}
+
+fn doors(definition: &Definition) -> Vec<Door> {
+    definition.doors.iter().map(Door::new).collect()
+}
+
+fn drawbridges(definition: &Definition) -> Vec<Drawbridge> {
+    definition.drawbridges.iter().map(Drawbridge::new).collect()
+}
+
+fn gears(definition: &Definition) -> Vec<Gear> {
+    definition.gears.iter().map(Gear::new).collect()
+}
+
+/// Mechanism operated with the crank, identified by its index
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operated {
+    Lift(usize),
+    Pump(usize),
+    Gate(usize),
+    Drawbridge(usize),
+    Gear(usize),
+}
+
+impl From<gear::Target> for Operated {
+    fn from(target: gear::Target) -> Self {
+        match target {
+            gear::Target::Lift(i) => Self::Lift(i),
+            gear::Target::Pump(i) => Self::Pump(i),
+            gear::Target::Gate(i) => Self::Gate(i),
+            gear::Target::Drawbridge(i) => Self::Drawbridge(i),
+        }
+    }
+}
+
+/// Boxes of the terrain cells around `area`
+fn terrain_boxes(terrain: &TrackedGrid<Cell>, area: Aabb) -> impl Iterator<Item = Aabb> + '_ {
+    coords(area)
+        .filter(|c| terrain.get(*c).is_some_and(Cell::is_solid))
+        .map(|[x, y]| Aabb::from_min_max([x as f32, y as f32], [(x + 1) as f32, (y + 1) as f32]))
+}
+
 fn coords(bounding_box: Aabb) -> impl Iterator<Item = [usize; 2]> {
     let [min_x, max_x] = bounding_box.x.into();
     let [min_y, max_y] = bounding_box.y.into();
@@ -146,7 +520,15 @@ pub struct Definition {
     pub foreground: [Image; 2],
     pub player_start: Vector,
     pub grid: Grid<Cell>,
-    pub lifts: Vec<(Vector, Option<Vector>, f32)>,
+    pub lifts: Vec<lift::Definition>,
+    pub pumps: Vec<pump::Definition>,
+    pub doors: Vec<door::Definition>,
+    pub drawbridges: Vec<drawbridge::Definition>,
+    pub gears: Vec<gear::Definition>,
+    pub keys: Vec<key::Pickup>,
+    pub collectibles: Vec<Collectible>,
+    pub water_triggers: Vec<WaterTrigger>,
+    pub tuning: Tuning,
 }
 
 impl Definition {
@@ -156,7 +538,46 @@ impl Definition {
         let data = ldtk::Data::load(num as usize)?;
         let player_start = data.entities.player[0] / TILE_SIZE;
         let grid = ldtk::load_grid(num as usize, data.width / 16, data.height / 16)?;
-        let lifts = data.entities.lifts.into_iter().map(Into::into).collect();
+        let gear_targets = data.entities.gear_targets();
+        let mut keys = data.entities.keys.into_iter().map(Into::into).collect();
+        let lifts = ldtk::lifts(data.entities.lifts, &mut keys)?;
+        let pumps = data
+            .entities
+            .pumps
+            .into_iter()
+            .map(ldtk::Pump::into_definition)
+            .collect::<anyhow::Result<_>>()?;
+        let doors = data
+            .entities
+            .doors
+            .into_iter()
+            .map(|d| d.into_definition(&keys))
+            .collect::<anyhow::Result<_>>()?;
+        let drawbridges = data
+            .entities
+            .drawbridges
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let gears = data
+            .entities
+            .gears
+            .into_iter()
+            .map(|g| g.into_definition(&gear_targets))
+            .collect::<anyhow::Result<_>>()?;
+        let collectibles = data
+            .entities
+            .collectibles
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let water_triggers = data
+            .entities
+            .water_triggers
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let tuning = Tuning::load(&data.custom_fields)?;
         Ok(Self {
             num,
             background,
@@ -164,6 +585,14 @@ impl Definition {
             player_start,
             grid,
             lifts,
+            pumps,
+            doors,
+            drawbridges,
+            gears,
+            keys,
+            collectibles,
+            water_triggers,
+            tuning,
         })
     }
 
@@ -180,12 +609,29 @@ impl Definition {
     }
 }
 
+/// Area raising or lowering the water when the player enters it for the first time
+#[derive(Debug, Clone)]
+pub struct WaterTrigger {
+    pub area: Aabb,
+    /// Height (in tiles) by which the water is raised, or lowered if negative
+    pub shift: f32,
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum Cell {
     #[default]
     Empty,
     Terrain,
     Hazard,
+    /// Terrain that disappears shortly after the player stands on it
+    Crumbling,
+}
+
+impl Cell {
+    pub fn is_solid(&self) -> bool {
+        matches!(self, Self::Terrain | Self::Crumbling)
+    }
 }
 
 mod ldtk;
+mod terrain;
@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use collision::Aabb;
+use crankit_graphics::{
+    image::{Flip, Image},
+    sheet::SpriteSheet,
+    Color, Rect,
+};
+use grid::{autotile, Grid, TrackedGrid};
+use timer::Timer;
+
+use crate::TILE_SIZE;
+
+use super::{coords, Cell};
+
+/// Time between the player stepping on a crumbling cell and its disappearance
+const CRUMBLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Image of the terrain, re-drawn where the terrain changes
+pub struct TerrainImage {
+    image: Image,
+    /// Cells missing from the image exported by LDtk, drawn on the next [`Self::redraw`]
+    pending: Vec<[usize; 2]>,
+}
+
+impl TerrainImage {
+    /// Starts from the `image` exported by LDtk, which does not contain the crumbling cells of `terrain`
+    pub fn new(image: Image, terrain: &Grid<Cell>) -> Self {
+        let pending = (0..terrain.height())
+            .flat_map(|y| (0..terrain.width()).map(move |x| [x, y]))
+            .filter(|c| matches!(terrain.get(*c), Some(Cell::Crumbling)))
+            .collect();
+        Self { image, pending }
+    }
+
+    /// Re-draws the dirty cells of `terrain` and their neighbors (whose edges may have changed), then marks them clean
+    pub fn redraw(&mut self, terrain: &mut TrackedGrid<Cell>, tiles: &SpriteSheet) {
+        if !self.pending.is_empty() {
+            let pending = core::mem::take(&mut self.pending);
+            self.draw_cells(terrain, tiles, pending.into_iter());
+        }
+        let Some([[min_x, min_y], [max_x, max_y]]) = terrain.dirty_region() else {
+            return;
+        };
+        let max_x = (max_x + 1).min(terrain.width() - 1);
+        let max_y = (max_y + 1).min(terrain.height() - 1);
+        let region = (min_y.saturating_sub(1)..=max_y)
+            .flat_map(|y| (min_x.saturating_sub(1)..=max_x).map(move |x| [x, y]));
+        self.draw_cells(terrain, tiles, region);
+        terrain.clear_dirty();
+    }
+
+    pub fn draw(&self) {
+        self.image.draw([0, 0]);
+    }
+
+    fn draw_cells(
+        &mut self,
+        terrain: &Grid<Cell>,
+        tiles: &SpriteSheet,
+        cells: impl Iterator<Item = [usize; 2]>,
+    ) {
+        crankit_graphics::with_draw_context(&mut self.image, || {
+            for coord in cells {
+                draw_cell(terrain, tiles, coord);
+            }
+        });
+    }
+}
+
+/// Draws the cell at `coord`, leaving the hazards as they were exported
+fn draw_cell(terrain: &Grid<Cell>, tiles: &SpriteSheet, coord: [usize; 2]) {
+    let cell = terrain.get(coord);
+    if matches!(cell, Some(Cell::Hazard) | None) {
+        return;
+    }
+    let size = TILE_SIZE as i32;
+    let [x, y] = coord;
+    let top_left = [x as i32 * size, y as i32 * size];
+    let rect = Rect::new(top_left, [size, size]);
+    crankit_graphics::fill_rect(rect, Color::clear());
+    let tile = autotile::tile_at(terrain, coord, &autotile::TILEMAP_STONE, Cell::is_solid);
+    if let Some(tile) = tile {
+        tiles.draw(tile as usize, top_left, Flip::Unflipped);
+    }
+    if matches!(cell, Some(Cell::Crumbling)) {
+        let [left, top] = top_left;
+        let [right, bottom] = [left + size - 1, top + size - 1];
+        crankit_graphics::draw_line([left + 3, top], [left + 8, bottom], 1, Color::xor());
+        crankit_graphics::draw_line([right - 2, top + 4], [left + 8, top + 9], 1, Color::xor());
+    }
+}
+
+/// Crumbling cells the player stepped on, that disappear once their delay is over
+#[derive(Debug, Default)]
+pub struct Crumbles {
+    cells: Vec<([usize; 2], Timer)>,
+}
+
+impl Crumbles {
+    /// Start the delay of the crumbling cells right under `player_box`
+    pub fn touch(&mut self, terrain: &Grid<Cell>, player_box: Aabb) {
+        let [min_x, max_x] = player_box.x.into();
+        let [_, max_y] = player_box.y.into();
+        let feet = Aabb::from_min_max([min_x, max_y], [max_x, max_y + 0.1]);
+        for coord @ [x, y] in coords(feet) {
+            let cell_box =
+                Aabb::from_min_max([x as f32, y as f32], [(x + 1) as f32, (y + 1) as f32]);
+            let is_crumbling = matches!(terrain.get(coord), Some(Cell::Crumbling));
+            let is_under = cell_box.collides(feet);
+            if is_crumbling && is_under && !self.cells.iter().any(|(c, _)| *c == coord) {
+                self.cells.push((coord, Timer::once(CRUMBLE_DELAY)));
+            }
+        }
+    }
+
+    /// Removes from `terrain` the cells whose delay is over
+    pub fn update(&mut self, delta_time: Duration, terrain: &mut TrackedGrid<Cell>) {
+        self.cells.retain_mut(|(coord, delay)| {
+            delay.update(delta_time);
+            if delay.is_finished() {
+                terrain.set(*coord, Cell::Empty);
+            }
+            !delay.is_finished()
+        });
+    }
+}
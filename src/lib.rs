@@ -5,7 +5,7 @@ extern crate alloc;
 #[cfg(feature = "draw-fps")]
 use alloc::vec::Vec;
 use core::time::Duration;
-use crankit_input::{ButtonsStateSource, CrankStateSource};
+use crankit_input::{Button, ButtonsStateSource, CrankStateSource};
 use crankit_time::ElapsedTime;
 use playdate_sys::ffi::PlaydateAPI;
 
@@ -14,16 +14,42 @@ use anyhow::anyhow;
 use playdate_sys::println;
 
 use crankit_game_loop::game_loop;
-use crankit_graphics::{image::Image, Color};
+use crankit_graphics::{
+    image::Image,
+    sheet::{GridLayout, SpriteSheet},
+    Color,
+};
 use level::Definition;
+use math2d::Degrees;
 
-use crate::level::Level;
+use crate::{
+    level::{Event, Level},
+    mechanism::Crank,
+    records::Records,
+    results::Results,
+};
 
 mod animation;
+mod collectible;
+#[cfg(feature = "debug-menu")]
+mod debug_menu;
+mod door;
+mod drawbridge;
+mod gear;
+mod key;
 mod level;
 mod lift;
+mod mechanism;
+mod platform;
 mod player;
+mod pump;
+mod records;
+mod results;
+mod shake;
+mod swim;
+mod tuning;
 mod water;
+mod wave;
 
 type Vector = math2d::Vector<f32>;
 type IVector = math2d::Vector<i32>;
@@ -32,11 +58,24 @@ const TILE_SIZE: f32 = 16.0;
 const SCREEN_WIDTH: i32 = 400;
 const SCREEN_HEIGHT: i32 = 240;
 
+/// Transformation from world coordinates (in tiles) to screen coordinates (in pixels)
+const WORLD_TO_SCREEN: math2d::Transform = math2d::Transform::from_uniform_scale(TILE_SIZE);
+
+/// Returns the screen pixel nearest to the given world `position`
+fn to_screen(position: Vector) -> IVector {
+    WORLD_TO_SCREEN
+        .transform_position(position)
+        .round()
+        .as_vector_i32()
+}
+
 struct Images {
     player: player::Images,
     water: water::Images,
     lift: Image,
     key: Image,
+    /// Tiles of the terrain, indexed as in LDtk
+    tiles: SpriteSheet,
 }
 
 impl Images {
@@ -48,11 +87,16 @@ impl Images {
         let lift =
             Image::load("img/lift").map_err(|err| anyhow!("cannot load lift image: {err}"))?;
         let key = Image::load("img/key").map_err(|err| anyhow!("cannot load key image: {err}"))?;
+        let tilemap = Image::load("img/tilemap")
+            .map_err(|err| anyhow!("cannot load tilemap image: {err}"))?;
+        let tile_size = TILE_SIZE as i32;
+        let tiles = SpriteSheet::from_grid(tilemap, GridLayout::new([tile_size, tile_size]));
         Ok(Self {
             player,
             water,
             lift,
             key,
+            tiles,
         })
     }
 }
@@ -61,15 +105,20 @@ struct Game {
     images: Images,
     thank_you_image: Image,
     level: Option<Level>,
+    /// Shown when the current level is completed, until the player continues to the next one
+    results: Option<Results>,
+    records: Records,
     #[cfg(feature = "draw-fps")]
     frame_durations: Vec<Duration>,
+    #[cfg(feature = "debug-menu")]
+    debug_menu: debug_menu::DebugMenu,
 }
 
 #[cfg(feature = "draw-fps")]
 const FRAME_WINDOW: usize = 30;
 
 impl crankit_game_loop::Game for Game {
-    fn new(_: &PlaydateAPI) -> Self {
+    fn new(playdate: &PlaydateAPI) -> Self {
         let level = Definition::load(0).unwrap().into();
         let images = Images::load().unwrap();
         let thank_you_image = Image::load("img/thanks").unwrap();
@@ -77,8 +126,12 @@ impl crankit_game_loop::Game for Game {
             images,
             thank_you_image,
             level: Some(level),
+            results: None,
+            records: Records::load(playdate),
             #[cfg(feature = "draw-fps")]
             frame_durations: Vec::with_capacity(FRAME_WINDOW),
+            #[cfg(feature = "debug-menu")]
+            debug_menu: debug_menu::DebugMenu::default(),
         }
     }
 
@@ -99,22 +152,53 @@ impl crankit_game_loop::Game for Game {
 
 impl Game {
     fn update(&mut self, delta_time: Duration, playdate: &PlaydateAPI) {
+        if self.results.is_some() {
+            if playdate.buttons_state().is_just_pressed(Button::A) {
+                self.results = None;
+                self.level = self.level.take().and_then(|l| {
+                    l.next().unwrap_or_else(|err| {
+                        playdate_sys::println!("failed to load the next level: {err}");
+                        None
+                    })
+                });
+            }
+            return;
+        }
         if let Some(level) = &mut self.level {
             let buttons = playdate.buttons_state();
-            let crank_change = playdate.crank_change_deg();
-            level.update(delta_time, buttons, crank_change);
-            if level.is_over() {
-                self.level = self.level.take().and_then(|l| l.next());
+            #[cfg(feature = "debug-menu")]
+            if self.debug_menu.update(buttons, level.tuning_mut()) {
+                return;
+            }
+            let crank = Crank {
+                change: Degrees(playdate.crank_change_deg()),
+                is_docked: is_crank_docked(playdate),
+            };
+            if let Some(Event::Completed(stats)) =
+                level.update(&self.images, delta_time, buttons, crank)
+            {
+                let num = level.num();
+                let results = Results::new(num, stats, self.records.best_time(num));
+                if results.is_new_best() {
+                    self.records.set_best_time(num, stats.time);
+                    if let Err(err) = self.records.save(playdate) {
+                        playdate_sys::println!("{err}");
+                    }
+                }
+                self.results = Some(results);
             }
         }
     }
 
     fn draw(&mut self) {
-        match &self.level {
-            None => self.thank_you_image.draw([0, 0]),
-            Some(level) => {
+        match (&self.level, &self.results) {
+            (_, Some(results)) => results.draw(),
+            (None, None) => self.thank_you_image.draw([0, 0]),
+            (Some(level), None) => {
                 crankit_graphics::clear(Color::black());
-                level.draw(&self.images)
+                level.draw(&self.images);
+                #[cfg(feature = "debug-menu")]
+                self.debug_menu.draw(level.tuning());
             }
         }
         #[cfg(feature = "draw-fps")]
@@ -122,4 +206,14 @@ impl Game {
     }
 }
 
+fn is_crank_docked(playdate: &PlaydateAPI) -> bool {
+    unsafe {
+        playdate
+            .system
+            .as_ref()
+            .and_then(|system| system.isCrankDocked)
+            .is_some_and(|is_docked| is_docked() != 0)
+    }
+}
+
 game_loop!(Game);
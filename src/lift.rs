@@ -1,21 +1,54 @@
+use alloc::vec::Vec;
 use core::time::Duration;
 
-use libm::fabsf;
+use serde::Deserialize;
 
 use collision::Aabb;
 use crankit_graphics::image::Image;
 
-use crate::player::Player;
-use crate::{IVector, Vector, TILE_SIZE};
+use crate::{
+    key::{Inventory, Key, Lock},
+    mechanism::{Drive, Mechanism},
+    platform::Platform,
+    to_screen, IVector, Vector,
+};
+
+/// What a lift does when it is not cranked
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+pub enum Behavior {
+    /// Go back to the start of its path
+    #[default]
+    Fall,
+    /// Hold its position
+    Ratchet,
+}
+
+/// Description of a lift, as placed in the level
+#[derive(Debug, Clone)]
+pub struct Definition {
+    /// Position of the lift at the bottom (or start) of its path
+    pub start: Vector,
+    /// Position of the lift at the top (or end) of its path
+    pub end: Vector,
+    /// Keys to carry before the lift can be used, at least one
+    ///
+    /// A lift without keys is a static platform.
+    pub required_keys: Vec<Key>,
+    pub behavior: Behavior,
+    /// Maximum speed when cranked, in tiles per second
+    pub max_crank_speed: Option<f32>,
+    /// Index of another lift that moves in the opposite direction
+    pub counterweight: Option<usize>,
+}
 
 #[derive(Debug)]
 pub struct Lift {
-    base: Vector,
-    can_be_active: bool,
-    key: Option<Vector>,
-    height: f32,
-    current: f32,
-    active: bool,
+    platform: Platform,
+    /// `None` for a static platform
+    lock: Option<Lock>,
+    behavior: Behavior,
+    max_crank_speed: Option<f32>,
+    counterweight: Option<usize>,
 }
 
 /// Position of the top left-corner of the image relative to the lift position
@@ -33,61 +66,106 @@ const INTERACTION_BOX_TOP_LEFT: Vector = Vector::new(-1.5, -2.);
 /// Bottom-right of the interaction box relative to the lift position
 const INTERACTION_BOX_BOTTOM_RIGHT: Vector = Vector::new(1.5, -1.);
 
-const SPEED_FACTOR: f32 = 0.01;
+/// Position of the center of the lock (while closed) relative to the lift position, in pixels
+const LOCK_CENTER: IVector = IVector::new(0, -8);
+
+/// Tunable parameters of the lifts
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Distance (in tiles) travelled up per degree of crank rotation
+    pub crank_speed_factor: f32,
+    /// Speed (in tiles per second) at which an inactive lift goes down
+    pub descent_speed: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            crank_speed_factor: 0.01,
+            descent_speed: 2.,
+        }
+    }
+}
 
 impl Lift {
-    pub fn new(base: Vector, key: Option<Vector>, height: f32) -> Self {
+    pub fn new(definition: &Definition) -> Self {
+        let is_locked = !definition.required_keys.is_empty();
+        let mut platform = Platform::new(definition.start, definition.end, 0.);
+        if is_locked {
+            // Wait at the end of the path until unlocked
+            platform.move_along(platform.length() - 1.);
+        }
         Self {
-            base,
-            can_be_active: key.is_some(),
-            key,
-            height,
-            current: if key.is_none() {
-                0.0
-            } else {
-                (height - 1.0).max(0.0)
-            },
-            active: false,
+            platform,
+            lock: is_locked.then(|| Lock::new(definition.required_keys.clone())),
+            behavior: definition.behavior,
+            max_crank_speed: definition.max_crank_speed,
+            counterweight: definition.counterweight,
         }
     }
 
-    pub fn set_active(&mut self, active: bool) {
-        self.active = self.can_be_active && active && self.key.is_none();
+    /// How the crank rotation moves the lift up, in tiles
+    pub fn drive(&self, config: &Config) -> Drive {
+        Drive {
+            ratio: config.crank_speed_factor,
+            max_speed: self.max_crank_speed,
+            is_reversible: false,
+        }
     }
 
-    pub fn update(&mut self, delta_time: Duration, crank_speed: f32, player: &mut Player) {
-        if self.active {
-            self.move_up(crank_speed, player);
+    /// Index of the lift moving in the opposite direction, if any
+    pub fn counterweight(&self) -> Option<usize> {
+        self.counterweight
+    }
+
+    /// Move the lift and returns the distance travelled toward the end of its path
+    ///
+    /// `cranked` is the movement given by the [`Self::drive`] if the lift is the operated mechanism.
+    /// The lift unlocks once the player carries the required keys in its `inventory`.
+    /// It does not move by more than `max_distance` toward the end of the path, which is used to
+    /// not move further than its counterweight can. Riders are not moved, see [`crate::platform::move_body`].
+    pub fn update(
+        &mut self,
+        delta_time: Duration,
+        cranked: Option<f32>,
+        inventory: &Inventory,
+        config: &Config,
+        max_distance: f32,
+    ) -> f32 {
+        if let Some(lock) = &mut self.lock {
+            lock.unlock(inventory);
+        }
+        if let Some(distance) = cranked {
+            self.platform.move_along(distance.min(max_distance))
+        } else if !self.is_unlocked() {
+            0.
+        } else if self.behavior == Behavior::Fall && self.counterweight.is_none() {
+            self.move_down(delta_time, config)
         } else {
-            match self.key {
-                None => self.move_down(delta_time),
-                Some(key) => self.collide_key(player, key),
-            }
+            0.
         }
     }
 
-    fn collide_key(&mut self, player: &mut Player, key: Vector) {
-        if Aabb::from_min_max(key, key + Vector::new(1., 1.)).collides(player.collision_box()) {
-            self.key = None;
-        }
+    /// Move along the path by `distance` (used for counterweights)
+    ///
+    /// Returns the distance actually travelled.
+    pub fn move_along(&mut self, distance: f32) -> f32 {
+        self.platform.move_along(distance)
     }
 
-    fn move_down(&mut self, delta_time: Duration) {
-        self.current = (self.current - delta_time.as_secs_f32() * 2.0).max(0.0)
+    /// Distance the lift can travel toward the start of its path
+    pub fn distance_to_start(&self) -> f32 {
+        self.platform.progress()
     }
 
-    fn move_up(&mut self, crank_speed: f32, player: &mut Player) {
-        let previous = self.current;
-        self.current = (self.current + fabsf(crank_speed) * SPEED_FACTOR).clamp(0.0, self.height);
-        player.move_by(Vector::new(0.0, previous - self.current))
+    fn is_unlocked(&self) -> bool {
+        self.lock.as_ref().is_some_and(Lock::is_open)
     }
 
-    pub fn interaction_box(&self) -> Aabb {
-        let pos = self.position();
-        Aabb::from_min_max(
-            pos + INTERACTION_BOX_TOP_LEFT,
-            pos + INTERACTION_BOX_BOTTOM_RIGHT,
-        )
+    fn move_down(&mut self, delta_time: Duration, config: &Config) -> f32 {
+        self.platform
+            .move_along(-delta_time.as_secs_f32() * config.descent_speed)
     }
 
     pub fn collision_box(&self) -> Aabb {
@@ -98,17 +176,29 @@ impl Lift {
         )
     }
 
-    pub fn draw(&self, lift_image: &Image, key_image: &Image) {
-        let pos = (self.position() * TILE_SIZE).as_vector_i32() + IMAGE_TOP_LEFT;
-        lift_image.draw(pos);
-        if let Some(key) = self.key.map(|p| (p * TILE_SIZE).as_vector_i32()) {
-            key_image.draw(key);
+    pub fn draw(&self, image: &Image) {
+        let pos = to_screen(self.position());
+        image.draw(pos + IMAGE_TOP_LEFT);
+        if let Some(lock) = &self.lock {
+            lock.draw(pos + LOCK_CENTER);
         }
     }
 
-    fn position(&self) -> Vector {
-        let mut pos = self.base;
-        pos.y -= self.current;
-        pos
+    pub fn position(&self) -> Vector {
+        self.platform.position()
+    }
+}
+
+impl Mechanism for Lift {
+    fn interaction_box(&self) -> Aabb {
+        let pos = self.position();
+        Aabb::from_min_max(
+            pos + INTERACTION_BOX_TOP_LEFT,
+            pos + INTERACTION_BOX_BOTTOM_RIGHT,
+        )
+    }
+
+    fn is_operable(&self) -> bool {
+        self.is_unlocked()
     }
 }
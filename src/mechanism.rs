@@ -0,0 +1,96 @@
+use core::time::Duration;
+
+use collision::Aabb;
+use crankit_input::{Button, ButtonsState};
+use math2d::{Degrees, Rect};
+
+/// Rotation speed when using the buttons instead of the crank, in degrees per second
+const FALLBACK_SPEED: f32 = 360.;
+
+/// State of the crank for the current frame
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Crank {
+    /// Rotation of the crank since the last frame
+    pub change: Degrees,
+    pub is_docked: bool,
+}
+
+impl Crank {
+    /// Rotation to apply to the mechanisms
+    ///
+    /// When the crank is docked, holding up (or down) rotates forward (or backward) instead.
+    pub fn rotation(self, buttons: ButtonsState, delta_time: Duration) -> Degrees {
+        if !self.is_docked {
+            return self.change;
+        }
+        let speed = if buttons.is_pressed(Button::Up) {
+            FALLBACK_SPEED
+        } else if buttons.is_pressed(Button::Down) {
+            -FALLBACK_SPEED
+        } else {
+            0.
+        };
+        Degrees(speed * delta_time.as_secs_f32())
+    }
+}
+
+/// How the crank rotation is converted into the movement of a mechanism
+#[derive(Debug, Copy, Clone)]
+pub struct Drive {
+    /// Movement per degree of rotation
+    pub ratio: f32,
+    /// Maximum movement per second
+    pub max_speed: Option<f32>,
+    /// If false, turning the crank in either direction moves the mechanism forward
+    pub is_reversible: bool,
+}
+
+impl Drive {
+    /// Movement for the crank `rotation` during `delta_time`
+    pub fn movement(self, Degrees(rotation): Degrees, delta_time: Duration) -> f32 {
+        let rotation = if self.is_reversible {
+            rotation
+        } else {
+            libm::fabsf(rotation)
+        };
+        let movement = rotation * self.ratio;
+        match self.max_speed {
+            Some(max_speed) => {
+                // A negative (or NaN) limit would make the clamp panic
+                let max = max_speed.max(0.) * delta_time.as_secs_f32();
+                movement.clamp(-max, max)
+            }
+            None => movement,
+        }
+    }
+}
+
+/// Something the player can operate with the crank
+pub trait Mechanism {
+    /// Area the player must be in to operate the mechanism
+    fn interaction_box(&self) -> Aabb;
+
+    /// Returns false if the mechanism cannot be operated at the moment (e.g. it is locked)
+    fn is_operable(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the key of the operable mechanism closest to the player, among the ones it is in the interaction box of
+///
+/// Only this mechanism should receive the crank rotation.
+pub fn select<'a, K>(
+    player_box: Aabb,
+    mechanisms: impl IntoIterator<Item = (K, &'a dyn Mechanism)>,
+) -> Option<K> {
+    let player_center = Rect::from(player_box).center();
+    mechanisms
+        .into_iter()
+        .filter(|(_, m)| m.is_operable() && m.interaction_box().collides(player_box))
+        .map(|(key, m)| {
+            let center = Rect::from(m.interaction_box()).center();
+            (key, (center - player_center).magnitude_squared())
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(key, _)| key)
+}
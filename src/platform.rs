@@ -0,0 +1,120 @@
+use collision::Aabb;
+
+use crate::Vector;
+
+/// Maximum distance between the bottom of a body and the top of a platform for the body to be considered standing on it
+const RIDE_TOLERANCE: f32 = 0.05;
+
+/// A platform moving back and forth along a straight path
+///
+/// This is the movement shared by every lift type: vertical, horizontal or diagonal.
+#[derive(Debug, Clone)]
+pub struct Platform {
+    start: Vector,
+    end: Vector,
+    length: f32,
+    /// Distance travelled from the start, between `0` and `length`
+    progress: f32,
+}
+
+impl Platform {
+    /// Create a platform that moves from `start` to `end`, initially at `progress` tiles from `start`
+    pub fn new(start: Vector, end: Vector, progress: f32) -> Self {
+        let length = (end - start).magnitude();
+        Self {
+            start,
+            end,
+            length,
+            progress: progress.clamp(0., length),
+        }
+    }
+
+    /// Move by `distance` tiles toward the end (or toward the start if negative)
+    ///
+    /// Returns the distance actually travelled, which is smaller if an end of the path is reached.
+    pub fn move_along(&mut self, distance: f32) -> f32 {
+        let previous = self.progress;
+        self.progress = (self.progress + distance).clamp(0., self.length);
+        self.progress - previous
+    }
+
+    /// Displacement corresponding to `distance` tiles travelled toward the end
+    fn displacement(&self, distance: f32) -> Vector {
+        if self.length > 0. {
+            (self.end - self.start) * (distance / self.length)
+        } else {
+            Vector::ZERO
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// Distance travelled from the start
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    pub fn position(&self) -> Vector {
+        self.start + self.displacement(self.progress)
+    }
+}
+
+/// Something that can be carried, pushed and crushed by moving platforms
+pub trait Body {
+    fn collision_box(&self) -> Aabb;
+    fn move_by(&mut self, delta: Vector);
+}
+
+/// How a body was affected by the movement of a platform
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Contact {
+    /// The body does not touch the platform
+    None,
+    /// The body was standing on the platform and moved with it
+    Carried,
+    /// The platform moved into the body, which was pushed out of the way
+    Pushed,
+    /// The body was carried or pushed into another solid
+    Crushed,
+}
+
+/// Move `body` according to a solid platform that moved by `displacement` from `previous_box`
+///
+/// A body standing on the platform is carried, and a body in the way of the platform is pushed
+/// out of it. `is_blocked` tells if a box collides with any solid other than the platform (such as
+/// the terrain), which decides if the body gets crushed. A carried body that is blocked horizontally
+/// is left behind instead of being crushed, so it can slide off the platform.
+pub fn move_body(
+    previous_box: Aabb,
+    displacement: Vector,
+    body: &mut impl Body,
+    is_blocked: impl Fn(Aabb) -> bool,
+) -> Contact {
+    let body_box = body.collision_box();
+    let contact = if is_standing_on(body_box, previous_box) {
+        let horizontal = Vector::new(displacement.x, 0.);
+        if !is_blocked(body_box.translated(horizontal)) {
+            body.move_by(horizontal);
+        }
+        body.move_by(Vector::new(0., displacement.y));
+        Contact::Carried
+    } else if let Some(penetration) = body_box.penetration(previous_box.translated(displacement)) {
+        body.move_by(penetration.into());
+        Contact::Pushed
+    } else {
+        return Contact::None;
+    };
+    if is_blocked(body.collision_box()) {
+        Contact::Crushed
+    } else {
+        contact
+    }
+}
+
+fn is_standing_on(body: Aabb, platform: Aabb) -> bool {
+    let [_, bottom] = body.y.into();
+    let [top, _] = platform.y.into();
+    body.x.collides(platform.x) && libm::fabsf(bottom - top) <= RIDE_TOLERANCE
+}
@@ -1,18 +1,27 @@
 use core::time::Duration;
 
 use anyhow::anyhow;
+use serde::Deserialize;
 
 use collision::Aabb;
-use crankit_graphics::image::{Flip, Image};
+use crankit_graphics::{
+    image::{Flip, Image},
+    sheet::{GridLayout, SpriteSheet},
+};
 use crankit_input::{Button, ButtonsState};
+use math2d::lerp;
+use timer::Timer;
 
-use crate::{animation::Animation, IVector, Vector, TILE_SIZE};
+use crate::{
+    animation::{Animation, Clip, Event, Sheet},
+    key::{Inventory, Key},
+    platform::Body,
+    swim::{self, Oxygen},
+    to_screen, IVector, Vector, TILE_SIZE,
+};
 
-const RUN_SPEED: f32 = 5.;
-const ANIMATION_FPS: f32 = 10.0;
-const RUN_ANIMATION_LEN: usize = 4;
-const JUMP_VELOCITY: f32 = 10.;
-const GRAVITY: f32 = 25.;
+/// Time to wait after the end of the dying animation before the player is dead
+const DEATH_DELAY: Duration = Duration::from_millis(400);
 
 /// Top-left of the collision bounding box relative to the player position
 const COLLISION_BOX_TOP_LEFT: Vector = Vector::new(-6. / TILE_SIZE, -12. / TILE_SIZE);
@@ -20,44 +29,143 @@ const COLLISION_BOX_TOP_LEFT: Vector = Vector::new(-6. / TILE_SIZE, -12. / TILE_
 /// Bottom-right of the collision bounding box relative to the player position
 const COLLISION_BOX_BOTTOM_RIGHT: Vector = Vector::new(6. / TILE_SIZE, 0.);
 
+/// Height of the player, used to know how much of it is under water
+const HEIGHT: f32 = COLLISION_BOX_BOTTOM_RIGHT.y - COLLISION_BOX_TOP_LEFT.y;
+
+/// Parameters of the player movement
+///
+/// Distances are in tiles and durations in seconds.
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MovementConfig {
+    /// Maximum horizontal speed
+    pub run_speed: f32,
+    /// Horizontal acceleration on the ground, when a direction is pressed
+    pub ground_acceleration: f32,
+    /// Horizontal deceleration on the ground, when no direction is pressed
+    pub ground_deceleration: f32,
+    /// Horizontal acceleration in the air, when a direction is pressed
+    pub air_acceleration: f32,
+    /// Horizontal deceleration in the air, when no direction is pressed
+    pub air_deceleration: f32,
+    pub gravity: f32,
+    /// Vertical speed at the start of a jump
+    pub jump_velocity: f32,
+    /// Factor applied to the vertical speed when the jump button is released before the top of the jump
+    pub jump_cut_factor: f32,
+    /// Time after walking off a ledge during which jumping is still possible
+    pub coyote_time: f32,
+    /// Time before landing during which a jump press is remembered
+    pub jump_buffer: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            run_speed: 5.,
+            ground_acceleration: 50.,
+            ground_deceleration: 60.,
+            air_acceleration: 30.,
+            air_deceleration: 10.,
+            gravity: 25.,
+            jump_velocity: 10.,
+            jump_cut_factor: 0.5,
+            coyote_time: 0.08,
+            jump_buffer: 0.1,
+        }
+    }
+}
+
+const ANIMATION_SHEET: &str = include_str!("../assets/animations/player.json");
+
 pub struct Images {
     /// Vector from the origin of the player to the top-left of the images
     top_left: IVector,
-    idle: Image,
-    running: [Image; RUN_ANIMATION_LEN],
-    falling: Image,
+    frames: SpriteSheet,
+    clips: Clips,
 }
 
 impl Images {
     pub fn load() -> anyhow::Result<Self> {
-        let sheet = &Image::load("img/player-sheet")
+        let sheet = Sheet::parse(ANIMATION_SHEET)?;
+        let image = Image::load("img/player-sheet")
             .map_err(|err| anyhow!("cannot load player images: {err}"))?;
-        let mut images = sheet.split_columns(7);
-        let idle = images.next().unwrap();
-        let running = [
-            images.next().unwrap(),
-            images.next().unwrap(),
-            images.next().unwrap(),
-            images.next().unwrap(),
-        ];
-        let falling = images.next().unwrap();
-        let _dying = images.next().unwrap();
-        let [w, h] = idle.size();
-        let top_left = IVector::new(-w / 2, -h);
+        let layout = GridLayout::from_columns_rows(image.size(), [sheet.columns, 1]);
+        let frames = SpriteSheet::from_grid(image, layout);
+        let [w, h] = frames
+            .frame(0)
+            .ok_or_else(|| anyhow!("player sheet has no frame"))?
+            .size;
+        let top_left = match sheet.pivot {
+            Some([x, y]) => IVector::new(-x, -y),
+            None => IVector::new(-w / 2, -h),
+        };
+        let clips = Clips {
+            idle: sheet.clip("idle")?,
+            run: sheet.clip("run")?,
+            jump: sheet.clip("jump")?,
+            fall: sheet.clip("fall")?,
+            die: sheet.clip("die")?,
+        };
         Ok(Self {
             top_left,
-            idle,
-            running,
-            falling,
+            frames,
+            clips,
         })
     }
 }
 
+struct Clips {
+    idle: Clip,
+    run: Clip,
+    jump: Clip,
+    fall: Clip,
+    die: Clip,
+}
+
+impl Clips {
+    fn get(&self, state: State) -> &Clip {
+        match state {
+            State::Idle => &self.idle,
+            State::Run => &self.run,
+            State::Jump => &self.jump,
+            State::Fall => &self.fall,
+            State::Die => &self.die,
+        }
+    }
+}
+
+/// State of the player, which decides the animation clip being played
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum State {
+    Idle,
+    Run,
+    Jump,
+    Fall,
+    Die,
+}
+
 pub struct Player {
     position: Vector,
     velocity: Vector,
     is_on_ground: bool,
-    run_animation: Option<Animation>,
+    /// Direction pressed (`-1`, `0` or `1`)
+    direction_input: f32,
+    is_jump_held: bool,
+    /// True from the start of a jump until it starts falling or is cut
+    is_jumping: bool,
+    /// Running while the player is on the ground, and for the coyote time after leaving it
+    coyote: Option<Timer>,
+    /// Running after the jump button is pressed, until the jump happens or the buffer time is elapsed
+    jump_buffer: Option<Timer>,
+    /// Fraction of the player height under water, between `0` and `1`
+    submersion: f32,
+    oxygen: Oxygen,
+    inventory: Inventory,
+    state: State,
+    animation: Animation,
+    /// Started when the dying animation is over
+    death_delay: Option<Timer>,
 }
 
 impl Player {
@@ -66,30 +174,154 @@ impl Player {
             position,
             is_on_ground: false,
             velocity: Vector::ZERO,
-            run_animation: None,
+            direction_input: 0.,
+            is_jump_held: false,
+            is_jumping: false,
+            coyote: None,
+            jump_buffer: None,
+            submersion: 0.,
+            oxygen: Oxygen::new(),
+            inventory: Inventory::default(),
+            state: State::Fall,
+            animation: Animation::default(),
+            death_delay: None,
         }
     }
 
-    pub fn handle_input(&mut self, buttons: ButtonsState) {
-        let jump = buttons.is_just_pressed(Button::A);
-        if jump && self.is_on_ground {
-            self.velocity.y = -JUMP_VELOCITY;
-            self.is_on_ground = false;
+    pub fn handle_input(&mut self, buttons: ButtonsState, movement: &MovementConfig) {
+        if self.is_dying() {
+            return;
+        }
+        if buttons.is_just_pressed(Button::A) {
+            self.jump_buffer = Some(Timer::once(Duration::from_secs_f32(movement.jump_buffer)));
         }
-        self.velocity.x = horizontal_speed_input(buttons);
+        self.is_jump_held = buttons.is_pressed(Button::A);
+        self.direction_input = direction_input(buttons);
     }
 
-    pub fn update(&mut self, delta_time: Duration) {
-        self.update_animation(delta_time);
+    /// Move the player, with the water surface at the vertical position `water_surface`
+    pub fn update(
+        &mut self,
+        delta_time: Duration,
+        images: &Images,
+        movement: &MovementConfig,
+        swim: &swim::Config,
+        water_surface: f32,
+    ) {
+        if self.is_dying() {
+            self.update_death(delta_time, images);
+            return;
+        }
+        self.submersion = ((self.position.y - water_surface) / HEIGHT).clamp(0., 1.);
+        self.oxygen.update(delta_time, self.is_under_water(), swim);
+        self.update_animation(delta_time, images);
+        self.update_jump(delta_time, movement, swim);
         let delta_seconds = delta_time.as_secs_f32();
-        self.velocity.y += GRAVITY * delta_seconds;
+        self.velocity.x = self.horizontal_velocity(delta_seconds, movement, swim);
+        self.velocity.y += self.vertical_acceleration(movement, swim) * delta_seconds;
+        if self.is_in_water() {
+            self.velocity.y = self.velocity.y.min(swim.max_sink_speed);
+        }
         self.position += self.velocity * delta_seconds;
+        // Set back by `on_floor_hit` if the player is still on the ground
+        self.is_on_ground = false;
+    }
+
+    fn update_jump(
+        &mut self,
+        delta_time: Duration,
+        movement: &MovementConfig,
+        swim: &swim::Config,
+    ) {
+        if self.is_on_ground {
+            self.coyote = Some(Timer::once(Duration::from_secs_f32(movement.coyote_time)));
+        } else {
+            update_optional_timer(&mut self.coyote, delta_time);
+        }
+        update_optional_timer(&mut self.jump_buffer, delta_time);
+        if self.jump_buffer.is_some() && self.coyote.is_some() {
+            self.velocity.y = -movement.jump_velocity;
+            self.is_jumping = true;
+            self.jump_buffer = None;
+            self.coyote = None;
+        } else if self.jump_buffer.is_some() && self.is_in_water() {
+            self.velocity.y = -swim.stroke_velocity;
+            self.jump_buffer = None;
+        }
+        if self.is_jumping && (self.velocity.y >= 0. || !self.is_jump_held) {
+            if self.velocity.y < 0. {
+                self.velocity.y *= movement.jump_cut_factor;
+            }
+            self.is_jumping = false;
+        }
+    }
+
+    fn horizontal_velocity(
+        &self,
+        delta_seconds: f32,
+        movement: &MovementConfig,
+        swim: &swim::Config,
+    ) -> f32 {
+        let speed_factor = lerp(1., swim.speed_factor, self.submersion);
+        let target = self.direction_input * movement.run_speed * speed_factor;
+        let rate = match (self.is_on_ground, self.direction_input == 0.) {
+            (true, false) => movement.ground_acceleration,
+            (true, true) => movement.ground_deceleration,
+            (false, false) => movement.air_acceleration,
+            (false, true) => movement.air_deceleration,
+        };
+        move_towards(self.velocity.x, target, rate * delta_seconds)
+    }
+
+    /// Gravity, reduced and countered by buoyancy as the player gets under water
+    fn vertical_acceleration(&self, movement: &MovementConfig, swim: &swim::Config) -> f32 {
+        let gravity = movement.gravity * lerp(1., swim.gravity_factor, self.submersion);
+        gravity - swim.buoyancy * self.submersion
+    }
+
+    fn is_in_water(&self) -> bool {
+        self.submersion > 0.
+    }
+
+    fn is_under_water(&self) -> bool {
+        self.submersion >= 1.
+    }
+
+    /// Returns true if the player stayed under water until running out of oxygen
+    pub fn has_drowned(&self) -> bool {
+        self.oxygen.is_empty()
+    }
+
+    /// Stop the player and play the dying animation (see [`Self::is_dead`])
+    pub fn die(&mut self) {
+        if !self.is_dying() {
+            self.state = State::Die;
+            self.animation = Animation::default();
+            self.velocity = Vector::ZERO;
+        }
+    }
+
+    pub fn is_dying(&self) -> bool {
+        self.state == State::Die
+    }
+
+    /// Returns true once the dying animation, and the delay that follows, are over
+    pub fn is_dead(&self) -> bool {
+        self.death_delay.as_ref().is_some_and(Timer::is_completed)
     }
 
     pub fn position(&self) -> Vector {
         self.position
     }
 
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    pub fn collect(&mut self, key: Key) {
+        self.inventory.add(key);
+    }
+
     pub fn move_by(&mut self, delta: Vector) {
         self.position += delta;
     }
@@ -114,42 +346,99 @@ impl Player {
         )
     }
 
-    fn update_animation(&mut self, delta_time: Duration) {
-        let is_running = self.is_on_ground && libm::fabsf(self.velocity.x) > 0.0;
-        match (is_running, &mut self.run_animation) {
-            (true, None) => {
-                self.run_animation = Some(Animation::new(RUN_ANIMATION_LEN, ANIMATION_FPS))
+    fn update_animation(&mut self, delta_time: Duration, images: &Images) {
+        let state = self.next_state();
+        if state != self.state {
+            self.state = state;
+            self.animation = Animation::default();
+        }
+        self.animation
+            .update(images.clips.get(self.state), delta_time);
+    }
+
+    fn update_death(&mut self, delta_time: Duration, images: &Images) {
+        match &mut self.death_delay {
+            Some(delay) => delay.update(delta_time),
+            None => {
+                let event = self
+                    .animation
+                    .update(images.clips.get(State::Die), delta_time);
+                // A looping clip never finishes, so its first loop counts as the end of the animation
+                if matches!(event, Some(Event::Finished | Event::Looped)) {
+                    self.death_delay = Some(Timer::once(DEATH_DELAY));
+                }
             }
-            (true, Some(animation)) => animation.update(delta_time),
-            (false, Some(_)) => self.run_animation = None,
-            (false, None) => (),
         }
     }
 
-    pub fn draw(&self, images: &Images) {
-        let image = if !self.is_on_ground {
-            &images.falling
-        } else if let Some(anim) = &self.run_animation {
-            &images.running[anim.current_frame]
+    fn next_state(&self) -> State {
+        if !self.is_on_ground {
+            if self.velocity.y < 0. {
+                State::Jump
+            } else {
+                State::Fall
+            }
+        } else if libm::fabsf(self.velocity.x) > 0.0 {
+            State::Run
         } else {
-            &images.idle
-        };
-        let pos = (self.position * TILE_SIZE).as_vector_i32() + images.top_left;
+            State::Idle
+        }
+    }
+
+    pub fn draw(&self, images: &Images) {
+        let index = self.animation.sheet_index(images.clips.get(self.state));
+        let pos = to_screen(self.position) + images.top_left;
         let flip = if self.velocity.x < 0.0 {
             Flip::FlippedX
         } else {
             Flip::Unflipped
         };
-        image.draw_with_flip(pos, flip);
+        images.frames.draw(index, pos, flip);
+    }
+
+    /// Draws the oxygen bubbles, to be drawn after the water
+    pub fn draw_hud(&self) {
+        if !self.is_dying() {
+            self.oxygen.draw(self.position);
+        }
     }
 }
 
-fn horizontal_speed_input(buttons: ButtonsState) -> f32 {
+impl Body for Player {
+    fn collision_box(&self) -> Aabb {
+        Player::collision_box(self)
+    }
+
+    fn move_by(&mut self, delta: Vector) {
+        Player::move_by(self, delta);
+    }
+}
+
+fn direction_input(buttons: ButtonsState) -> f32 {
     if buttons.is_pressed(Button::Right) {
-        RUN_SPEED
+        1.
     } else if buttons.is_pressed(Button::Left) {
-        -RUN_SPEED
+        -1.
     } else {
         0.
     }
 }
+
+/// Update the timer, and remove it once completed
+fn update_optional_timer(timer: &mut Option<Timer>, delta_time: Duration) {
+    if let Some(t) = timer {
+        t.update(delta_time);
+        if t.is_completed() {
+            *timer = None;
+        }
+    }
+}
+
+/// Returns `current` moved toward `target` by at most `max_delta`
+fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    if current < target {
+        (current + max_delta).min(target)
+    } else {
+        (current - max_delta).max(target)
+    }
+}
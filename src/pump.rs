@@ -0,0 +1,109 @@
+use core::time::Duration;
+
+use collision::Aabb;
+use crankit_graphics::{Color, Rect};
+use math2d::{Angle, Degrees};
+use serde::Deserialize;
+
+use crate::{
+    mechanism::{Drive, Mechanism},
+    to_screen, IVector, Vector,
+};
+
+/// Radius of the wheel, in pixels
+const WHEEL_RADIUS: i32 = 7;
+
+/// Center of the wheel relative to the pump position, in pixels
+const WHEEL_CENTER: IVector = IVector::new(0, -8);
+
+/// Top-left of the interaction box relative to the pump position
+const INTERACTION_BOX_TOP_LEFT: Vector = Vector::new(-1., -1.);
+
+/// Bottom-right of the interaction box relative to the pump position
+const INTERACTION_BOX_BOTTOM_RIGHT: Vector = Vector::new(1., 0.);
+
+/// Tunable parameters of the pumps
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Height (in tiles) by which the water is lowered per degree of crank rotation
+    pub crank_speed_factor: f32,
+    /// Maximum speed (in tiles per second) at which the water is lowered
+    pub max_crank_speed: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            crank_speed_factor: 0.005,
+            max_crank_speed: 1.,
+        }
+    }
+}
+
+/// Description of a pump, as placed in the level
+#[derive(Debug, Clone)]
+pub struct Definition {
+    /// Bottom-center of the pump
+    pub position: Vector,
+    /// Overrides [`Config::crank_speed_factor`]
+    pub ratio: Option<f32>,
+    /// Overrides [`Config::max_crank_speed`]
+    pub max_speed: Option<f32>,
+}
+
+/// Wheel that lowers the water when cranked
+#[derive(Debug)]
+pub struct Pump {
+    position: Vector,
+    ratio: Option<f32>,
+    max_speed: Option<f32>,
+    /// Angle of the wheel, which turns with the crank
+    angle: Degrees,
+}
+
+impl Pump {
+    pub fn new(definition: &Definition) -> Self {
+        Self {
+            position: definition.position,
+            ratio: definition.ratio,
+            max_speed: definition.max_speed,
+            angle: Degrees(0.),
+        }
+    }
+
+    /// Turn the wheel by the crank `rotation`, and returns the height by which the water should be lowered
+    pub fn crank(&mut self, rotation: Degrees, delta_time: Duration, config: &Config) -> f32 {
+        self.angle = (self.angle + rotation).normalize_positive();
+        self.drive(config).movement(rotation, delta_time)
+    }
+
+    fn drive(&self, config: &Config) -> Drive {
+        Drive {
+            ratio: self.ratio.unwrap_or(config.crank_speed_factor),
+            max_speed: Some(self.max_speed.unwrap_or(config.max_crank_speed)),
+            is_reversible: false,
+        }
+    }
+
+    pub fn draw(&self) {
+        let center = to_screen(self.position) + WHEEL_CENTER;
+        let top_left = center - IVector::new(WHEEL_RADIUS, WHEEL_RADIUS);
+        let size = [WHEEL_RADIUS * 2, WHEEL_RADIUS * 2];
+        crankit_graphics::draw_ellipse(Rect::new(top_left, size), 2, Color::white());
+        let spoke = IVector::new(
+            libm::roundf(self.angle.cos() * WHEEL_RADIUS as f32) as i32,
+            libm::roundf(self.angle.sin() * WHEEL_RADIUS as f32) as i32,
+        );
+        crankit_graphics::draw_line(center - spoke, center + spoke, 2, Color::white());
+    }
+}
+
+impl Mechanism for Pump {
+    fn interaction_box(&self) -> Aabb {
+        Aabb::from_min_max(
+            self.position + INTERACTION_BOX_TOP_LEFT,
+            self.position + INTERACTION_BOX_BOTTOM_RIGHT,
+        )
+    }
+}
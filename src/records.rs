@@ -0,0 +1,97 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    ffi::{c_char, c_uint, c_void},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use playdate_sys::ffi::{FileOptions, PlaydateAPI};
+
+/// Path of the records in the game data folder
+const PATH: &[u8] = b"records.json\0";
+
+const READ_CHUNK_SIZE: usize = 256;
+
+/// Best completion time of each level, kept in the game data folder
+#[derive(Debug, Clone, Default)]
+pub struct Records {
+    /// In milliseconds, by level number
+    best_times: BTreeMap<u8, u64>,
+}
+
+impl Records {
+    /// Load the saved records, or returns empty records if there are none (or they cannot be read)
+    pub fn load(playdate: &PlaydateAPI) -> Self {
+        let best_times = read(playdate)
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default();
+        Self { best_times }
+    }
+
+    pub fn best_time(&self, level_num: u8) -> Option<Duration> {
+        self.best_times
+            .get(&level_num)
+            .map(|&millis| Duration::from_millis(millis))
+    }
+
+    pub fn set_best_time(&mut self, level_num: u8, time: Duration) {
+        self.best_times.insert(level_num, time.as_millis() as u64);
+    }
+
+    pub fn save(&self, playdate: &PlaydateAPI) -> anyhow::Result<()> {
+        let raw = serde_json::to_vec(&self.best_times)
+            .map_err(|err| anyhow!("failed to serialize records: {err}"))?;
+        unsafe {
+            let file = playdate
+                .file
+                .as_ref()
+                .ok_or_else(|| anyhow!("file API not available"))?;
+            let open = file
+                .open
+                .ok_or_else(|| anyhow!("file open function not available"))?;
+            let write = file
+                .write
+                .ok_or_else(|| anyhow!("file write function not available"))?;
+            let close = file
+                .close
+                .ok_or_else(|| anyhow!("file close function not available"))?;
+            let handle = open(PATH.as_ptr().cast::<c_char>(), FileOptions::kFileWrite);
+            if handle.is_null() {
+                return Err(anyhow!("failed to open records file"));
+            }
+            let written = write(handle, raw.as_ptr().cast::<c_void>(), raw.len() as c_uint);
+            close(handle);
+            if written < 0 {
+                return Err(anyhow!("failed to write records file"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Content of the records file, if it exists
+fn read(playdate: &PlaydateAPI) -> Option<Vec<u8>> {
+    unsafe {
+        let file = playdate.file.as_ref()?;
+        let (open, read, close) = (file.open?, file.read?, file.close?);
+        let handle = open(PATH.as_ptr().cast::<c_char>(), FileOptions::kFileReadData);
+        if handle.is_null() {
+            return None;
+        }
+        let mut raw = Vec::new();
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            let count = read(
+                handle,
+                chunk.as_mut_ptr().cast::<c_void>(),
+                READ_CHUNK_SIZE as c_uint,
+            );
+            if count <= 0 {
+                break;
+            }
+            raw.extend_from_slice(&chunk[..count as usize]);
+        }
+        close(handle);
+        Some(raw)
+    }
+}
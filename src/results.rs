@@ -0,0 +1,80 @@
+use alloc::{format, string::String};
+use core::time::Duration;
+
+use crankit_graphics::Color;
+
+/// Top-left of the first line of text, in pixels
+const POSITION: [i32; 2] = [60, 50];
+
+const LINE_HEIGHT: i32 = 24;
+
+/// Statistics of a completed level
+#[derive(Debug, Copy, Clone)]
+pub struct Stats {
+    /// Time spent in the level, including the failed attempts
+    pub time: Duration,
+    pub deaths: u32,
+    /// Collectibles found in the successful attempt
+    pub collectibles: usize,
+    pub total_collectibles: usize,
+}
+
+/// Screen shown between levels
+#[derive(Debug, Clone)]
+pub struct Results {
+    level_num: u8,
+    stats: Stats,
+    /// Best time before this completion, if the level was completed before
+    previous_best: Option<Duration>,
+}
+
+impl Results {
+    pub fn new(level_num: u8, stats: Stats, previous_best: Option<Duration>) -> Self {
+        Self {
+            level_num,
+            stats,
+            previous_best,
+        }
+    }
+
+    pub fn is_new_best(&self) -> bool {
+        !self
+            .previous_best
+            .is_some_and(|best| self.stats.time >= best)
+    }
+
+    pub fn draw(&self) {
+        crankit_graphics::clear(Color::white());
+        let best = if self.is_new_best() {
+            String::from("New best time!")
+        } else {
+            format!(
+                "Best: {}",
+                format_time(self.previous_best.unwrap_or_default())
+            )
+        };
+        let lines = [
+            format!("Level {} complete!", self.level_num + 1),
+            format!("Time: {}", format_time(self.stats.time)),
+            best,
+            format!("Deaths: {}", self.stats.deaths),
+            format!(
+                "Collectibles: {}/{}",
+                self.stats.collectibles, self.stats.total_collectibles
+            ),
+            String::from("Press A to continue"),
+        ];
+        let [x, y] = POSITION;
+        lines.iter().enumerate().for_each(|(i, line)| {
+            crankit_graphics::draw_text(line, [x, y + i as i32 * LINE_HEIGHT]);
+        });
+    }
+}
+
+/// Formats `time` as minutes, seconds and hundredths (e.g. `1:05.42`)
+fn format_time(time: Duration) -> String {
+    let hundredths = time.as_millis() / 10;
+    let minutes = hundredths / 6000;
+    let seconds = hundredths / 100 % 60;
+    format!("{minutes}:{seconds:02}.{:02}", hundredths % 100)
+}
@@ -0,0 +1,38 @@
+use core::time::Duration;
+
+use math2d::Easing;
+use timer::Tween;
+
+use crate::IVector;
+
+/// Maximum offset of the screen, in pixels
+const AMPLITUDE: f32 = 4.;
+const DURATION: Duration = Duration::from_millis(400);
+
+/// Horizontal screen shake, fading out over time
+pub struct Shake {
+    amplitude: Tween<f32>,
+    direction: f32,
+}
+
+impl Shake {
+    pub fn new() -> Self {
+        Self {
+            amplitude: Tween::new(AMPLITUDE, 0., DURATION).with_easing(Easing::QuadOut),
+            direction: 1.,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: Duration) {
+        self.amplitude.update(delta_time);
+        self.direction = -self.direction;
+    }
+
+    /// Offset to apply to everything drawn on screen
+    pub fn offset(&self) -> IVector {
+        IVector::new(
+            libm::roundf(self.amplitude.value() * self.direction) as i32,
+            0,
+        )
+    }
+}
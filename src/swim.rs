@@ -0,0 +1,101 @@
+use core::time::Duration;
+
+use serde::Deserialize;
+
+use crankit_graphics::{Color, Rect};
+
+use crate::{to_screen, IVector, Vector};
+
+/// Tunable parameters of the player while in water
+///
+/// Distances are in tiles and durations in seconds.
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Factor applied to the gravity
+    pub gravity_factor: f32,
+    /// Upward acceleration when fully under water, proportionally less when partially under water
+    pub buoyancy: f32,
+    /// Maximum downward speed
+    pub max_sink_speed: f32,
+    /// Factor applied to the run speed
+    pub speed_factor: f32,
+    /// Vertical speed at the start of a swim stroke (pressing jump while in water)
+    pub stroke_velocity: f32,
+    /// Time the player can stay with the head under water
+    pub oxygen: f32,
+    /// How many times faster oxygen is recovered than it is consumed
+    pub oxygen_recovery: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            gravity_factor: 0.3,
+            buoyancy: 6.,
+            max_sink_speed: 2.,
+            speed_factor: 0.6,
+            stroke_velocity: 5.,
+            oxygen: 4.,
+            oxygen_recovery: 2.,
+        }
+    }
+}
+
+/// Number of bubbles displayed when the oxygen is full
+const BUBBLES: i32 = 5;
+const BUBBLE_SIZE: i32 = 6;
+const BUBBLE_SPACING: i32 = 2;
+
+/// Position of the center of the bubbles, relative to the player position
+const BUBBLES_OFFSET: IVector = IVector::new(0, -22);
+
+/// Oxygen of the player, consumed while the head is under water
+#[derive(Debug)]
+pub struct Oxygen {
+    /// Fraction of the oxygen left, between `0` and `1`
+    fraction: f32,
+}
+
+impl Oxygen {
+    pub fn new() -> Self {
+        Self { fraction: 1. }
+    }
+
+    pub fn update(&mut self, delta_time: Duration, is_under_water: bool, config: &Config) {
+        if config.oxygen <= 0. {
+            self.fraction = if is_under_water { 0. } else { 1. };
+            return;
+        }
+        let change = delta_time.as_secs_f32() / config.oxygen;
+        self.fraction = if is_under_water {
+            self.fraction - change
+        } else {
+            self.fraction + change * config.oxygen_recovery
+        }
+        .clamp(0., 1.);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fraction <= 0.
+    }
+
+    /// Draws the remaining oxygen as bubbles above the player, unless the oxygen is full
+    pub fn draw(&self, player_position: Vector) {
+        if self.fraction >= 1. {
+            return;
+        }
+        let count = libm::ceilf(self.fraction * BUBBLES as f32) as i32;
+        let width = BUBBLES * (BUBBLE_SIZE + BUBBLE_SPACING) - BUBBLE_SPACING;
+        let [x, y] = (to_screen(player_position) + BUBBLES_OFFSET).into();
+        let left = x - width / 2;
+        (0..count).for_each(|i| {
+            let top_left = [
+                left + i * (BUBBLE_SIZE + BUBBLE_SPACING),
+                y - BUBBLE_SIZE / 2,
+            ];
+            let rect = Rect::new(top_left, [BUBBLE_SIZE, BUBBLE_SIZE]);
+            crankit_graphics::draw_ellipse(rect, 1, Color::xor());
+        });
+    }
+}
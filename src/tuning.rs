@@ -0,0 +1,98 @@
+use alloc::string::String;
+
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{door, drawbridge, lift, player::MovementConfig, pump, swim, water};
+
+const RAW_TUNING: &str = include_str!("../assets/tuning.json");
+
+/// Physics parameters of the game
+///
+/// The defaults are read from `assets/tuning.json`, and can be overridden per level by LDtk level
+/// custom fields named after the section and the parameter in PascalCase (e.g. `PlayerRunSpeed`
+/// overrides `player.run_speed`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tuning {
+    pub player: MovementConfig,
+    pub swim: swim::Config,
+    pub water: water::Config,
+    pub lift: lift::Config,
+    pub pump: pump::Config,
+    pub drawbridge: drawbridge::Config,
+    pub gate: door::Config,
+}
+
+impl Tuning {
+    /// Load the tuning file, and apply the level `custom_fields` on top of it
+    ///
+    /// Custom fields that are neither numbers nor booleans are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the parameter if a custom field or a key of the tuning file does not
+    /// match any parameter, or if a duration or a speed limit is negative or not finite.
+    pub fn load(custom_fields: &Map<String, Value>) -> anyhow::Result<Self> {
+        let mut raw: Value = serde_json::from_str(RAW_TUNING)
+            .map_err(|err| anyhow!("failed to parse tuning file: {err}"))?;
+        for (name, value) in custom_fields
+            .iter()
+            .filter(|(_, value)| value.is_number() || value.is_boolean())
+        {
+            let snake_name = snake_case(name);
+            let Some((Value::Object(section), parameter)) = snake_name
+                .split_once('_')
+                .and_then(|(section, parameter)| Some((raw.get_mut(section)?, parameter)))
+            else {
+                return Err(anyhow!("invalid tuning: unknown parameter {name}"));
+            };
+            section.insert(parameter.into(), value.clone());
+        }
+        let tuning: Self =
+            serde_json::from_value(raw).map_err(|err| anyhow!("invalid tuning: {err}"))?;
+        tuning.validate()?;
+        Ok(tuning)
+    }
+
+    /// Checks the parameters that cannot be negative (durations, and the bounds of a clamp)
+    fn validate(&self) -> anyhow::Result<()> {
+        let non_negative = [
+            ("player.coyote_time", self.player.coyote_time),
+            ("player.jump_buffer", self.player.jump_buffer),
+            ("water.delay", self.water.delay),
+            ("water.shift_speed", self.water.shift_speed),
+            ("pump.max_crank_speed", self.pump.max_crank_speed),
+            (
+                "drawbridge.max_crank_speed",
+                self.drawbridge.max_crank_speed,
+            ),
+            ("gate.max_crank_speed", self.gate.max_crank_speed),
+        ];
+        match non_negative
+            .into_iter()
+            .find(|(_, value)| !(value.is_finite() && *value >= 0.))
+        {
+            Some((name, value)) => Err(anyhow!(
+                "invalid tuning: {name} must be a non-negative number, got {value}"
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !result.is_empty() {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
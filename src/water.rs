@@ -1,10 +1,15 @@
-use core::time::Duration;
+use core::{f32::consts::TAU, time::Duration};
 
 use anyhow::anyhow;
+use serde::Deserialize;
 
-use crankit_graphics::image::{self, Image};
+use crankit_graphics::{
+    image::{self, Image},
+    Rect,
+};
+use timer::{Stopwatch, Timer};
 
-use crate::{SCREEN_HEIGHT, SCREEN_WIDTH, TILE_SIZE};
+use crate::{wave::Waves, SCREEN_HEIGHT, SCREEN_WIDTH, TILE_SIZE};
 
 pub struct Images {
     height: i32,
@@ -27,37 +32,164 @@ impl Images {
     }
 }
 
+/// Tunable parameters of the water
+///
+/// Heights and distances are in tiles, durations in seconds.
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Height of the water when the level starts, from the bottom of the screen
+    pub start_height: f32,
+    /// Rising speed when the water starts rising, in tiles per second
+    pub raise_speed: f32,
+    /// Increase of the rising speed, in tiles per second squared
+    pub acceleration: f32,
+    /// Time before the water starts rising
+    pub delay: f32,
+    /// Stop rising while the player is cranking a lift
+    pub pause_while_cranking: bool,
+    /// Maximum height of the tide, above and below the water level
+    pub tide_amplitude: f32,
+    /// Duration of a full tide cycle (no tide if zero)
+    pub tide_period: f32,
+    /// Speed at which the water is raised or lowered by triggers, in tiles per second
+    pub shift_speed: f32,
+    /// Strength of the spring pulling the surface back to rest
+    pub wave_tension: f32,
+    /// How fast the waves fade out
+    pub wave_damping: f32,
+    /// How strongly each part of the surface pulls its neighbours, which propagates the waves
+    pub wave_spread: f32,
+    /// Speed of the surface (in pixels per second) per speed of what enters the water (in tiles per second)
+    pub splash_factor: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            start_height: 0.,
+            raise_speed: 0.2,
+            acceleration: 0.,
+            delay: 0.,
+            pause_while_cranking: false,
+            tide_amplitude: 0.,
+            tide_period: 0.,
+            shift_speed: 1.,
+            wave_tension: 20.,
+            wave_damping: 1.5,
+            wave_spread: 80.,
+            splash_factor: 10.,
+        }
+    }
+}
+
 pub struct Water {
+    /// Height of the water from the bottom of the screen, not including the tide
     level: f32,
+    /// Started when the level starts, the water does not rise before it is completed
+    delay: Timer,
+    /// Time spent rising, which drives the acceleration and the tide
+    rising: Stopwatch,
+    /// Height still to be added (or removed if negative) because of triggers
+    pending_shift: f32,
+    tide: f32,
+    waves: Waves,
 }
 
-const RAISE_SPEED: f32 = 0.2;
-
 /// Offset of the image relative to the level
 const IMAGE_OFFSET: i32 = 7;
+
+/// Width of the strips the surface is split into for the waves, in pixels
+const WAVE_COLUMN_WIDTH: i32 = 4;
+
 impl Water {
-    pub fn new() -> Self {
-        Self { level: 0.0 }
+    pub fn new(config: &Config) -> Self {
+        Self {
+            level: config.start_height,
+            delay: Timer::once(Duration::from_secs_f32(config.delay)),
+            rising: Stopwatch::new(),
+            pending_shift: 0.,
+            tide: 0.,
+            waves: Waves::new(SCREEN_WIDTH, WAVE_COLUMN_WIDTH),
+        }
+    }
+
+    /// Raise the water by `amount` tiles (or lower it if negative), progressively
+    pub fn shift(&mut self, amount: f32) {
+        self.pending_shift += amount;
+    }
+
+    pub fn update(&mut self, delta_time: Duration, config: &Config, is_cranking: bool) {
+        self.waves.update(delta_time, config);
+        let delta_seconds = delta_time.as_secs_f32();
+        let shift = self.pending_shift.clamp(
+            -config.shift_speed * delta_seconds,
+            config.shift_speed * delta_seconds,
+        );
+        self.pending_shift -= shift;
+        self.level = (self.level + shift).max(0.);
+        self.delay.update(delta_time);
+        if !self.delay.is_completed() || (config.pause_while_cranking && is_cranking) {
+            return;
+        }
+        self.rising.update(delta_time);
+        let rising_seconds = self.rising.elapsed().as_secs_f32();
+        let speed = config.raise_speed + config.acceleration * rising_seconds;
+        self.level += speed * delta_seconds;
+        self.tide = if config.tide_period > 0. {
+            config.tide_amplitude * libm::sinf(TAU * rising_seconds / config.tide_period)
+        } else {
+            0.
+        };
+    }
+
+    /// Make a splash if something at the horizontal position `x` moved from `previous_y` to `y`
+    /// (all in tiles) through the surface during `delta_time`
+    pub fn splash_on_entry(
+        &mut self,
+        x: f32,
+        previous_y: f32,
+        y: f32,
+        delta_time: Duration,
+        config: &Config,
+    ) {
+        let surface = self.vertical_position();
+        let delta_seconds = delta_time.as_secs_f32();
+        if previous_y <= surface && y > surface && delta_seconds > 0. {
+            let speed = (y - previous_y) / delta_seconds;
+            self.waves
+                .splash(x * TILE_SIZE, -speed * config.splash_factor);
+        }
     }
 
-    pub fn update(&mut self, delta_time: Duration) {
-        self.level += RAISE_SPEED * delta_time.as_secs_f32();
+    /// Current height of the water from the bottom of the screen
+    fn height(&self) -> f32 {
+        self.level + self.tide
     }
 
     pub fn vertical_position(&self) -> f32 {
-        (SCREEN_HEIGHT as f32 / TILE_SIZE) - self.level
+        (SCREEN_HEIGHT as f32 / TILE_SIZE) - self.height()
     }
 
+    /// Draws the water in vertical strips, each one shifted by the height of the wave at its position
     pub fn draw(&self, images: &Images) {
+        let rest_y = SCREEN_HEIGHT - (self.height() * TILE_SIZE) as i32 - IMAGE_OFFSET;
+        let width = self.waves.column_width();
         image::with_draw_mode(image::DrawMode::XOR, || {
-            let mut y = SCREEN_HEIGHT - (self.level * TILE_SIZE) as i32 - IMAGE_OFFSET;
-            images
-                .surface
-                .draw_tiled([0, y], [SCREEN_WIDTH, images.height]);
-            y += images.height;
-            images
-                .body
-                .draw_tiled([0, y], [SCREEN_WIDTH, SCREEN_HEIGHT - y]);
+            self.waves.columns().for_each(|(x, wave_height)| {
+                let y = rest_y - wave_height;
+                let strip = Rect::new([x, y], [width, SCREEN_HEIGHT - y]);
+                // Tiles are drawn from the left of the screen so that the strips are aligned
+                crankit_graphics::with_clip_rect(strip, || {
+                    images
+                        .surface
+                        .draw_tiled([0, y], [x + width, images.height]);
+                    let body_y = y + images.height;
+                    images
+                        .body
+                        .draw_tiled([0, body_y], [x + width, SCREEN_HEIGHT - body_y]);
+                });
+            });
         });
     }
 }
@@ -0,0 +1,83 @@
+use alloc::{vec, vec::Vec};
+use core::time::Duration;
+
+use crate::water::Config;
+
+/// Number of neighbour columns on each side also pushed by a splash
+const SPLASH_RADIUS: usize = 2;
+
+/// Spring-based simulation of waves along the water surface
+///
+/// The surface is split in columns of equal width, each attached by a spring to the rest level and
+/// to its neighbours. Heights are in pixels, positive upward.
+pub struct Waves {
+    column_width: i32,
+    columns: Vec<Column>,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+struct Column {
+    height: f32,
+    velocity: f32,
+}
+
+impl Waves {
+    /// Create a flat surface of `width` pixels split in columns of `column_width` pixels
+    pub fn new(width: i32, column_width: i32) -> Self {
+        let count = (width + column_width - 1) / column_width;
+        Self {
+            column_width,
+            columns: vec![Column::default(); count as usize],
+        }
+    }
+
+    pub fn update(&mut self, delta_time: Duration, config: &Config) {
+        let delta_seconds = delta_time.as_secs_f32();
+        let mut left = self.columns.first().map_or(0., |c| c.height);
+        for i in 0..self.columns.len() {
+            let height = self.columns[i].height;
+            let right = self.columns.get(i + 1).map_or(height, |c| c.height);
+            let column = &mut self.columns[i];
+            let acceleration = -config.wave_tension * height
+                - config.wave_damping * column.velocity
+                + config.wave_spread * (left + right - 2. * height);
+            column.velocity += acceleration * delta_seconds;
+            left = height;
+        }
+        self.columns
+            .iter_mut()
+            .for_each(|c| c.height += c.velocity * delta_seconds);
+    }
+
+    /// Push the surface at `x` (in pixels) with `velocity` (in pixels per second, positive upward)
+    pub fn splash(&mut self, x: f32, velocity: f32) {
+        let center = (x / self.column_width as f32) as isize;
+        for distance in 0..=SPLASH_RADIUS {
+            let falloff = 1. - distance as f32 / (SPLASH_RADIUS + 1) as f32;
+            let mut push = |index: isize| {
+                if let Some(column) = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| self.columns.get_mut(i))
+                {
+                    column.velocity += velocity * falloff;
+                }
+            };
+            push(center - distance as isize);
+            if distance > 0 {
+                push(center + distance as isize);
+            }
+        }
+    }
+
+    /// Returns the left position and the height (both in pixels) of each column
+    pub fn columns(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i as i32 * self.column_width, libm::roundf(c.height) as i32))
+    }
+
+    pub fn column_width(&self) -> i32 {
+        self.column_width
+    }
+}
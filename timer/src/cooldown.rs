@@ -0,0 +1,82 @@
+use core::time::Duration;
+
+/// Prevents an action from being performed again before a given duration is elapsed
+///
+/// A new cooldown is ready to be triggered.
+#[derive(Debug, Clone)]
+pub struct Cooldown {
+    duration: Duration,
+    remaining: Duration,
+}
+
+impl Cooldown {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            remaining: Duration::ZERO,
+        }
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        self.remaining = self.remaining.saturating_sub(elapsed);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Start the cooldown if it is ready
+    ///
+    /// Returns true if the cooldown was ready (meaning the action may be performed)
+    pub fn trigger(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        self.remaining = self.duration;
+        true
+    }
+
+    /// Time left before the cooldown is ready again
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Make the cooldown ready immediately
+    pub fn reset(&mut self) {
+        self.remaining = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_ready_initially() {
+        assert!(Cooldown::new(Duration::from_secs(1)).is_ready());
+    }
+
+    #[test]
+    fn cannot_be_triggered_again_before_duration() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(1));
+        assert!(cooldown.trigger());
+        assert!(!cooldown.is_ready());
+        cooldown.update(Duration::from_millis(999));
+        assert!(!cooldown.trigger());
+        assert_eq!(cooldown.remaining(), Duration::from_millis(1));
+        cooldown.update(Duration::from_millis(1));
+        assert!(cooldown.trigger());
+    }
+
+    #[test]
+    fn reset_makes_ready() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(1));
+        cooldown.trigger();
+        cooldown.reset();
+        assert!(cooldown.is_ready());
+    }
+}
@@ -1,33 +1,158 @@
 #![no_std]
 
+extern crate alloc;
+
 use core::time::Duration;
 
+pub use cooldown::Cooldown;
+pub use scheduler::{Handle, Scheduler};
+pub use stopwatch::Stopwatch;
+#[cfg(feature = "math2d")]
+pub use tween::{Repeat, Tween};
+
+mod cooldown;
+mod scheduler;
+mod stopwatch;
+#[cfg(feature = "math2d")]
+mod tween;
+
+/// Whether a [`Timer`] stops or starts over when it finishes
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Finish once, then stay finished until [`Timer::reset`] is called
+    Once,
+    /// Start over each time the duration is elapsed
+    #[default]
+    Repeating,
+}
+
+#[derive(Debug, Clone)]
 pub struct Timer {
-    total: Duration,
-    remaining: Duration,
-    finished: bool,
+    mode: Mode,
+    duration: Duration,
+    elapsed: Duration,
+    paused: bool,
+    completed: bool,
+    times_finished_this_tick: u32,
 }
 
 impl Timer {
+    /// Create a repeating timer
     pub fn new(duration: Duration) -> Self {
+        Self::with_mode(duration, Mode::Repeating)
+    }
+
+    /// Create a timer that finishes only once
+    pub fn once(duration: Duration) -> Self {
+        Self::with_mode(duration, Mode::Once)
+    }
+
+    pub fn with_mode(duration: Duration, mode: Mode) -> Self {
         Self {
-            total: duration,
-            remaining: duration,
-            finished: false,
+            mode,
+            duration,
+            elapsed: Duration::ZERO,
+            paused: false,
+            completed: false,
+            times_finished_this_tick: 0,
         }
     }
 
     pub fn update(&mut self, elapsed: Duration) {
-        self.finished = false;
-        while elapsed >= self.remaining {
-            self.finished = true;
-            self.remaining += self.total
+        self.times_finished_this_tick = 0;
+        if self.paused || self.completed {
+            return;
+        }
+        self.elapsed += elapsed;
+        match self.mode {
+            Mode::Once => {
+                if self.elapsed >= self.duration {
+                    self.elapsed = self.duration;
+                    self.completed = true;
+                    self.times_finished_this_tick = 1;
+                }
+            }
+            Mode::Repeating if self.duration.is_zero() => self.times_finished_this_tick = 1,
+            Mode::Repeating => {
+                while self.elapsed >= self.duration {
+                    self.times_finished_this_tick += 1;
+                    self.elapsed -= self.duration;
+                }
+            }
         }
-        self.remaining -= elapsed;
     }
 
+    /// Returns true if the timer finished during the last update
     pub fn is_finished(&self) -> bool {
-        self.finished
+        self.times_finished_this_tick > 0
+    }
+
+    /// Number of times the timer finished during the last update
+    ///
+    /// It can be greater than one for a repeating timer if the update was longer than the duration
+    pub fn times_finished_this_tick(&self) -> u32 {
+        self.times_finished_this_tick
+    }
+
+    /// Returns true if a [`Mode::Once`] timer has finished (in any update since the last reset)
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Change the duration, without resetting the elapsed time
+    ///
+    /// If the elapsed time is already greater than the new duration, the timer finishes on the next update.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Time elapsed since the timer was started (or since it last started over, for a repeating timer)
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Time left before the timer finishes
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    /// Progress toward the end of the timer, between `0` and `1`
+    pub fn fraction(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.
+        } else {
+            (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.)
+        }
+    }
+
+    /// Stop updating the timer until [`Self::resume`] is called
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Start the timer over, as if it was just created
+    ///
+    /// The paused state is left untouched.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.completed = false;
+        self.times_finished_this_tick = 0;
     }
 }
 
@@ -79,4 +204,78 @@ mod tests {
         timer.update(Duration::from_millis(300));
         assert!(timer.is_finished());
     }
+
+    #[test]
+    fn counts_times_finished_this_tick() {
+        let mut timer = Timer::new(Duration::from_millis(100));
+        timer.update(Duration::from_millis(350));
+        assert_eq!(timer.times_finished_this_tick(), 3);
+        assert_eq!(timer.elapsed(), Duration::from_millis(50));
+        timer.update(Duration::from_millis(10));
+        assert_eq!(timer.times_finished_this_tick(), 0);
+    }
+
+    #[test]
+    fn once_finishes_only_once() {
+        let mut timer = Timer::once(Duration::from_secs(1));
+        timer.update(Duration::from_millis(2500));
+        assert_eq!(timer.times_finished_this_tick(), 1);
+        assert!(timer.is_completed());
+        assert_eq!(timer.remaining(), Duration::ZERO);
+        timer.update(Duration::from_secs(1));
+        assert!(!timer.is_finished());
+        assert!(timer.is_completed());
+    }
+
+    #[test]
+    fn reset_restarts_once_timer() {
+        let mut timer = Timer::once(Duration::from_secs(1));
+        timer.update(Duration::from_secs(1));
+        timer.reset();
+        assert!(!timer.is_completed());
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        timer.update(Duration::from_secs(1));
+        assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn paused_timer_does_not_progress() {
+        let mut timer = Timer::new(Duration::from_secs(1));
+        timer.pause();
+        timer.update(Duration::from_secs(2));
+        assert!(!timer.is_finished());
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        timer.resume();
+        timer.update(Duration::from_secs(1));
+        assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut timer = Timer::once(Duration::from_secs(2));
+        timer.update(Duration::from_millis(500));
+        assert_eq!(timer.elapsed(), Duration::from_millis(500));
+        assert_eq!(timer.remaining(), Duration::from_millis(1500));
+        assert_eq!(timer.fraction(), 0.25);
+    }
+
+    #[test]
+    fn set_duration_keeps_elapsed_time() {
+        let mut timer = Timer::once(Duration::from_secs(2));
+        timer.update(Duration::from_millis(1500));
+        timer.set_duration(Duration::from_secs(1));
+        assert_eq!(timer.elapsed(), Duration::from_millis(1500));
+        assert_eq!(timer.fraction(), 1.);
+        timer.update(Duration::ZERO);
+        assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn zero_duration_finishes_every_update() {
+        let mut timer = Timer::new(Duration::ZERO);
+        timer.update(Duration::from_millis(16));
+        assert_eq!(timer.times_finished_this_tick(), 1);
+        timer.update(Duration::from_millis(16));
+        assert_eq!(timer.times_finished_this_tick(), 1);
+    }
 }
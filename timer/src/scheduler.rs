@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// Identifies an event scheduled in a [`Scheduler`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Handle(u32);
+
+/// Fires events after a delay or at a regular interval
+#[derive(Debug, Clone)]
+pub struct Scheduler<E> {
+    entries: Vec<Entry<E>>,
+    fired: Vec<(Duration, E)>,
+    next_id: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<E> {
+    handle: Handle,
+    remaining: Duration,
+    interval: Option<Duration>,
+    event: E,
+}
+
+impl<E> Default for Scheduler<E> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            fired: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<E: Clone> Scheduler<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule `event` to fire once, after `delay`
+    pub fn after(&mut self, delay: Duration, event: E) -> Handle {
+        self.schedule(delay, None, event)
+    }
+
+    /// Schedule `event` to fire every `interval`, starting after the first interval
+    pub fn every(&mut self, interval: Duration, event: E) -> Handle {
+        self.schedule(interval, Some(interval), event)
+    }
+
+    fn schedule(&mut self, delay: Duration, interval: Option<Duration>, event: E) -> Handle {
+        let handle = Handle(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.entries.push(Entry {
+            handle,
+            remaining: delay,
+            interval,
+            event,
+        });
+        handle
+    }
+
+    /// Remove a scheduled event
+    ///
+    /// Returns false if the event was not scheduled (anymore)
+    pub fn cancel(&mut self, handle: Handle) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.handle != handle);
+        self.entries.len() != len
+    }
+
+    pub fn is_scheduled(&self, handle: Handle) -> bool {
+        self.entries.iter().any(|entry| entry.handle == handle)
+    }
+
+    /// Remove all scheduled events
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Advance time, and returns the events that fired, in the order they fired
+    ///
+    /// A repeating event fires as many times as its interval is elapsed (but at most once per update if the interval is zero).
+    pub fn update(&mut self, elapsed: Duration) -> impl Iterator<Item = E> + '_ {
+        self.fired.clear();
+        self.entries.retain_mut(|entry| {
+            let mut fire_at = entry.remaining;
+            loop {
+                if fire_at > elapsed {
+                    entry.remaining = fire_at - elapsed;
+                    return true;
+                }
+                self.fired.push((fire_at, entry.event.clone()));
+                match entry.interval {
+                    None => return false,
+                    Some(interval) if interval.is_zero() => {
+                        entry.remaining = Duration::ZERO;
+                        return true;
+                    }
+                    Some(interval) => fire_at += interval,
+                }
+            }
+        });
+        self.fired.sort_by_key(|(fire_at, _)| *fire_at);
+        self.fired.drain(..).map(|(_, event)| event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fired(scheduler: &mut Scheduler<char>, millis: u64) -> Vec<char> {
+        scheduler.update(Duration::from_millis(millis)).collect()
+    }
+
+    #[test]
+    fn fires_once_after_delay() {
+        let mut scheduler = Scheduler::new();
+        scheduler.after(Duration::from_millis(100), 'a');
+        assert_eq!(fired(&mut scheduler, 99), []);
+        assert_eq!(fired(&mut scheduler, 1), ['a']);
+        assert_eq!(fired(&mut scheduler, 100), []);
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn fires_repeatedly_at_interval() {
+        let mut scheduler = Scheduler::new();
+        scheduler.every(Duration::from_millis(100), 'a');
+        assert_eq!(fired(&mut scheduler, 250), ['a', 'a']);
+        assert_eq!(fired(&mut scheduler, 50), ['a']);
+        assert_eq!(fired(&mut scheduler, 50), []);
+    }
+
+    #[test]
+    fn fires_in_chronological_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.after(Duration::from_millis(150), 'b');
+        scheduler.every(Duration::from_millis(100), 'a');
+        assert_eq!(fired(&mut scheduler, 200), ['a', 'b', 'a']);
+    }
+
+    #[test]
+    fn cancelled_event_does_not_fire() {
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.every(Duration::from_millis(100), 'a');
+        assert!(scheduler.is_scheduled(handle));
+        assert!(scheduler.cancel(handle));
+        assert!(!scheduler.cancel(handle));
+        assert_eq!(fired(&mut scheduler, 100), []);
+    }
+
+    #[test]
+    fn zero_interval_fires_once_per_update() {
+        let mut scheduler = Scheduler::new();
+        scheduler.every(Duration::ZERO, 'a');
+        assert_eq!(fired(&mut scheduler, 16), ['a']);
+        assert_eq!(fired(&mut scheduler, 16), ['a']);
+    }
+}
@@ -0,0 +1,70 @@
+use core::time::Duration;
+
+/// Externally driven measure of the time elapsed since it was started
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    elapsed: Duration,
+    paused: bool,
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        if !self.paused {
+            self.elapsed += elapsed;
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Stop measuring time until [`Self::resume`] is called
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set the elapsed time back to zero
+    ///
+    /// The paused state is left untouched.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_elapsed_time() {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.update(Duration::from_millis(300));
+        stopwatch.update(Duration::from_millis(200));
+        assert_eq!(stopwatch.elapsed(), Duration::from_millis(500));
+        stopwatch.reset();
+        assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn paused_stopwatch_does_not_progress() {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.pause();
+        stopwatch.update(Duration::from_millis(300));
+        assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+        stopwatch.resume();
+        stopwatch.update(Duration::from_millis(300));
+        assert_eq!(stopwatch.elapsed(), Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,151 @@
+use core::time::Duration;
+
+use math2d::{Easing, Lerp};
+
+use crate::{Mode, Timer};
+
+/// What a [`Tween`] does once it reaches its end
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Repeat {
+    /// Stay at the end value
+    #[default]
+    Once,
+    /// Start over from the start value
+    Loop,
+    /// Go back to the start value, then to the end again, and so on
+    PingPong,
+}
+
+/// Interpolates between two values over time
+#[derive(Debug, Clone)]
+pub struct Tween<T> {
+    start: T,
+    end: T,
+    easing: Easing,
+    repeat: Repeat,
+    timer: Timer,
+    reversed: bool,
+}
+
+impl<T: Lerp> Tween<T> {
+    /// Create a linear tween from `start` to `end` that runs once
+    pub fn new(start: T, end: T, duration: Duration) -> Self {
+        Self {
+            start,
+            end,
+            easing: Easing::Linear,
+            repeat: Repeat::Once,
+            timer: Timer::once(duration),
+            reversed: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    #[must_use]
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        let mode = match repeat {
+            Repeat::Once => Mode::Once,
+            Repeat::Loop | Repeat::PingPong => Mode::Repeating,
+        };
+        self.timer = Timer::with_mode(self.timer.duration(), mode);
+        self.reversed = false;
+        self
+    }
+
+    pub fn update(&mut self, elapsed: Duration) {
+        self.timer.update(elapsed);
+        if self.repeat == Repeat::PingPong && self.timer.times_finished_this_tick() % 2 == 1 {
+            self.reversed = !self.reversed;
+        }
+    }
+
+    /// Current interpolated value
+    pub fn value(&self) -> T {
+        let t = if self.timer.is_completed() {
+            1.
+        } else {
+            self.timer.fraction()
+        };
+        let t = if self.reversed { 1. - t } else { t };
+        self.start.lerp(self.end, self.easing.apply(t))
+    }
+
+    /// Returns true if a [`Repeat::Once`] tween has reached its end
+    pub fn is_finished(&self) -> bool {
+        self.timer.is_completed()
+    }
+
+    /// Go back to the start value
+    pub fn reset(&mut self) {
+        self.timer.reset();
+        self.reversed = false;
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math2d::Vector;
+
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn interpolates_from_start_to_end() {
+        let mut tween = Tween::new(0., 10., SECOND);
+        assert_eq!(tween.value(), 0.);
+        tween.update(SECOND / 4);
+        assert_eq!(tween.value(), 2.5);
+        tween.update(SECOND);
+        assert_eq!(tween.value(), 10.);
+        assert!(tween.is_finished());
+    }
+
+    #[test]
+    fn applies_easing() {
+        let mut tween = Tween::new(0., 1., SECOND).with_easing(Easing::QuadIn);
+        tween.update(SECOND / 2);
+        assert_eq!(tween.value(), 0.25);
+    }
+
+    #[test]
+    fn loops() {
+        let mut tween = Tween::new(0, 100, SECOND).with_repeat(Repeat::Loop);
+        tween.update(SECOND * 5 / 4);
+        assert_eq!(tween.value(), 25);
+        assert!(!tween.is_finished());
+    }
+
+    #[test]
+    fn ping_pongs() {
+        let mut tween = Tween::new(Vector::new(0., 0.), Vector::new(4., 8.), SECOND)
+            .with_repeat(Repeat::PingPong);
+        tween.update(SECOND * 5 / 4);
+        assert_eq!(tween.value(), Vector::new(3., 6.));
+        tween.update(SECOND);
+        assert_eq!(tween.value(), Vector::new(1., 2.));
+    }
+
+    #[test]
+    fn reset_goes_back_to_start() {
+        let mut tween = Tween::new(0., 1., SECOND);
+        tween.update(SECOND);
+        tween.reset();
+        assert_eq!(tween.value(), 0.);
+        assert!(!tween.is_finished());
+    }
+}